@@ -0,0 +1,237 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+
+// How the ends of a chain rule candidates out.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AicType {
+    // Both ends are the same digit, so it can be removed from every
+    // cell that sees both ends.
+    Type1,
+
+    // The ends are different digits in cells that see each other, so
+    // each end's cell can't hold the other end's digit.
+    Type2,
+}
+
+// An alternating inference chain: a list of candidates joined by
+// links that alternate strong, weak, strong, ..., strong.  Either the
+// first candidate or the last one has to be true.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AicPattern {
+    pub kind: AicType,
+    pub chain: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+}
+
+fn candidate_name(candidate: &Candidate) -> String {
+    format!("({}){}", candidate.1, cell_name(candidate.0))
+}
+
+impl fmt::Display for AicPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut chain = candidate_name(&self.chain[0]);
+        for (i, candidate) in self.chain.iter().enumerate().skip(1) {
+            chain += if i % 2 == 1 { " = " } else { " - " };
+            chain += &candidate_name(candidate);
+        }
+
+        write!(f, "{:?} {} removes {}",
+               self.kind, chain,
+               self.eliminations.iter().map(candidate_name).collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl Pattern for AicPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+}
+
+// Every candidate on the board, and the candidates each one is
+// strongly linked to: the other digit of a cell with only two, and
+// the other place for the digit in a group with only two.
+fn strong_links(board: &Board) -> HashMap<Candidate, BTreeSet<Candidate>> {
+    let mut result: HashMap<Candidate, BTreeSet<Candidate>> = HashMap::new();
+
+    for idx in 0..81 {
+        if matches!(board.cells[idx], Cell::Unsolved(_)) && board.cells[idx].count() == 2 {
+            let digits = board.cells[idx].digits().into_iter().collect::<Vec<usize>>();
+            result.entry((idx, digits[0])).or_default().insert((idx, digits[1]));
+            result.entry((idx, digits[1])).or_default().insert((idx, digits[0]));
+        }
+    }
+
+    for group in Board::all_groups() {
+        for digit in 1..=9 {
+            let places = board.places(&group, digit);
+            if places.len() == 2
+                && places.iter().all(|idx| matches!(board.cells[*idx], Cell::Unsolved(_)))
+            {
+                result.entry((places[0], digit)).or_default().insert((places[1], digit));
+                result.entry((places[1], digit)).or_default().insert((places[0], digit));
+            }
+        }
+    }
+
+    result
+}
+
+// The candidates ruled out if this one is true: the other digits in
+// its cell, and the same digit in every cell it sees.
+fn weak_links(board: &Board, candidate: Candidate) -> Vec<Candidate> {
+    let (idx, digit) = candidate;
+    let mut result = board.cells[idx]
+        .digits()
+        .into_iter()
+        .filter(|other| *other != digit)
+        .map(|other| (idx, other))
+        .collect::<Vec<Candidate>>();
+
+    result.extend(Board::all_neighbors(idx)
+                  .into_iter()
+                  .filter(|other| matches!(board.cells[*other], Cell::Unsolved(_))
+                          && board.cells[*other].contains(digit))
+                  .map(|other| (other, digit)));
+    result.sort_unstable();
+    result
+}
+
+// The candidates that can't be true if both ends of a chain are
+// candidates that might be.
+fn eliminations(board: &Board, first: Candidate, last: Candidate) -> Option<(AicType, Vec<Candidate>)> {
+    if first.1 == last.1 {
+        let eliminations = (0..81)
+            .filter(|idx| Board::sees(*idx, first.0)
+                    && Board::sees(*idx, last.0)
+                    && board.cells[*idx].contains(first.1))
+            .map(|idx| (idx, first.1))
+            .collect::<Vec<Candidate>>();
+
+        Some((AicType::Type1, eliminations))
+    } else if Board::sees(first.0, last.0) {
+        let eliminations = [(first.0, last.1), (last.0, first.1)]
+            .into_iter()
+            .filter(|(idx, digit)| board.cells[*idx].contains(*digit))
+            .collect::<Vec<Candidate>>();
+
+        Some((AicType::Type2, eliminations))
+    } else {
+        None
+    }
+}
+
+// Find chains with at most max_length candidates that have something
+// to eliminate.  Each start candidate is assumed false, which forces
+// its strong links true, which forces their weak links false, and so
+// on.  Every candidate reached as true is the end of a chain.
+pub fn find_aics(board: &Board, max_length: usize) -> Vec<AicPattern> {
+    let strong = strong_links(board);
+    let mut result = Vec::new();
+    let mut starts = strong.keys().copied().collect::<Vec<Candidate>>();
+    starts.sort_unstable();
+
+    for start in starts {
+        let mut parents = HashMap::new();
+        let mut todo = VecDeque::from([(start, false, 1)]);
+        let mut ends = Vec::new();
+
+        while let Some((candidate, on, length)) = todo.pop_front() {
+            if length >= max_length {
+                continue;
+            }
+
+            let next = if on {
+                weak_links(board, candidate)
+            } else {
+                strong.get(&candidate).into_iter().flatten().copied().collect()
+            };
+
+            for other in next {
+                if other == start {
+                    continue;
+                }
+
+                if let Entry::Vacant(entry) = parents.entry((other, !on)) {
+                    entry.insert((candidate, on));
+                    todo.push_back((other, !on, length + 1));
+                    if !on {
+                        ends.push(other);
+                    }
+                }
+            }
+        }
+
+        for end in ends.into_iter().filter(|end| *end > start) {
+            let Some((kind, eliminations)) = eliminations(board, start, end) else {
+                continue;
+            };
+
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            let mut chain = vec![end];
+            let mut state = (end, true);
+            while let Some(parent) = parents.get(&state) {
+                chain.push(parent.0);
+                state = *parent;
+            }
+            chain.reverse();
+
+            // The shortest way to reach a candidate can still pass
+            // through some other candidate twice.
+            if chain.iter().collect::<BTreeSet<_>>().len() != chain.len() {
+                continue;
+            }
+
+            result.push(AicPattern {
+                kind,
+                chain,
+                eliminations,
+            });
+        }
+    }
+
+    result
+}
+
+// Alternating inference chains over every candidate on the board,
+// which covers most of the named chain patterns in one search.  Long
+// chains are slow to find and hard to follow, so they are limited to
+// max_length candidates.
+pub struct Aic {
+    max_length: usize,
+}
+
+impl Aic {
+    pub const DEFAULT_MAX_LENGTH: usize = 12;
+
+    // Make an AIC strategy that only looks for chains of at most
+    // max_length candidates.
+    pub fn with_max_length(max_length: usize) -> Box<dyn Strategy> {
+        Box::new(Aic { max_length })
+    }
+}
+
+impl Strategy for Aic {
+    fn new() -> Box<dyn Strategy> {
+        Self::with_max_length(Self::DEFAULT_MAX_LENGTH)
+    }
+
+    fn name(&self) -> String {
+        String::from("AIC")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_aics(board, self.max_length))
+    }
+}
@@ -0,0 +1,288 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::candidate_names;
+use crate::cell_names;
+use crate::combinations;
+use crate::digit_names;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// An almost locked set: N unsolved cells in one group that between
+// them can only be N+1 digits.  Take away any one of the digits and
+// the rest are locked into the cells.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AlmostLockedSet {
+    pub cells: Vec<usize>,
+    pub digits: Vec<usize>,
+}
+
+impl AlmostLockedSet {
+    // The cells of the set that could be the digit.
+    pub fn places(&self, board: &Board, digit: usize) -> Vec<usize> {
+        board.places(&self.cells, digit)
+    }
+
+    pub fn contains(&self, digit: usize) -> bool {
+        self.digits.contains(&digit)
+    }
+
+    pub fn overlaps(&self, other: &AlmostLockedSet) -> bool {
+        self.cells.iter().any(|idx| other.cells.contains(idx))
+    }
+}
+
+impl fmt::Display for AlmostLockedSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{}}} at {}",
+               digit_names(&self.digits),
+               cell_names(&self.cells))
+    }
+}
+
+// Every almost locked set on the board, each listed once even when its
+// cells share more than one group.  A single cell with two digits
+// counts as an almost locked set of one.
+pub fn almost_locked_sets(board: &Board) -> Vec<AlmostLockedSet> {
+    let mut seen = BTreeSet::new();
+    let mut result = Vec::new();
+
+    for group in Board::all_groups() {
+        let unsolved = group
+            .into_iter()
+            .filter(|idx| matches!(board.cells[*idx], Cell::Unsolved(_)))
+            .collect::<Vec<usize>>();
+
+        for size in 1..=unsolved.len() {
+            for cells in combinations(&unsolved, size) {
+                let digits = cells
+                    .iter()
+                    .flat_map(|idx| board.cells[*idx].digits())
+                    .collect::<BTreeSet<usize>>();
+
+                if digits.len() == size + 1 && seen.insert(cells.clone()) {
+                    result.push(AlmostLockedSet {
+                        cells,
+                        digits: digits.into_iter().collect(),
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// The restricted common candidates of two sets that don't share any
+// cells: digits both could be, where every place for the digit in one
+// sees every place for it in the other.  At most one of the two sets
+// can hold such a digit.
+pub fn restricted_commons(board: &Board, a: &AlmostLockedSet, b: &AlmostLockedSet) -> Vec<usize> {
+    if a.overlaps(b) {
+        return Vec::new();
+    }
+
+    a.digits
+        .iter()
+        .filter(|digit| b.contains(**digit))
+        .filter(|digit| {
+            let places = b.places(board, **digit);
+            a.places(board, **digit)
+                .iter()
+                .all(|idx| places.iter().all(|other| Board::sees(*idx, *other)))
+        })
+        .copied()
+        .collect()
+}
+
+// The places for the digit that see every cell in cells that could be
+// the digit.
+pub fn seeing_all(board: &Board, cells: &[usize], digit: usize) -> Vec<Candidate> {
+    let places = board.places(cells, digit);
+
+    (0..81)
+        .filter(|idx| matches!(board.cells[*idx], Cell::Unsolved(_))
+                && board.cells[*idx].contains(digit)
+                && places.iter().all(|other| Board::sees(*idx, *other)))
+        .map(|idx| (idx, digit))
+        .collect()
+}
+
+// Two almost locked sets joined by a restricted common candidate x.
+// Only one of them can be x, so the other is locked, which means any
+// other digit z they share has to be in one of them, and can be
+// removed from every cell that sees all of their places for z.  When
+// they are joined by two restricted commons, both sets are locked: x
+// and y each have to be in one of them, and every other digit has to
+// be in its own set.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AlsXzPattern {
+    pub sets: [AlmostLockedSet; 2],
+    pub restricted: Vec<usize>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl fmt::Display for AlsXzPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} and {} are linked by {{{}}}, so {} can be removed",
+               self.sets[0], self.sets[1], digit_names(&self.restricted),
+               candidate_names(&self.eliminations))
+    }
+}
+
+impl Pattern for AlsXzPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+}
+
+pub fn find_als_xz(board: &Board) -> Vec<AlsXzPattern> {
+    let sets = almost_locked_sets(board);
+    let mut result = Vec::new();
+
+    for i in 0..sets.len() {
+        for j in i+1..sets.len() {
+            let (a, b) = (&sets[i], &sets[j]);
+            let restricted = restricted_commons(board, a, b);
+            if restricted.is_empty() {
+                continue;
+            }
+
+            let both = a.cells.iter().chain(&b.cells).copied().collect::<Vec<usize>>();
+            let mut eliminations = BTreeSet::new();
+
+            if restricted.len() == 1 {
+                for digit in a.digits.iter().filter(|digit| b.contains(**digit) && !restricted.contains(digit)) {
+                    eliminations.extend(seeing_all(board, &both, *digit));
+                }
+            } else {
+                for digit in &restricted {
+                    eliminations.extend(seeing_all(board, &both, *digit));
+                }
+                for set in [a, b] {
+                    for digit in set.digits.iter().filter(|digit| !restricted.contains(digit)) {
+                        eliminations.extend(seeing_all(board, &set.cells, *digit));
+                    }
+                }
+            }
+
+            if !eliminations.is_empty() {
+                result.push(AlsXzPattern {
+                    sets: [a.clone(), b.clone()],
+                    restricted,
+                    eliminations: eliminations.into_iter().collect(),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+// Two almost locked sets A and B that are both joined to a third, the
+// pivot C, by different restricted common candidates x and y.  If A
+// isn't locked it holds x, so C can't, so C holds y and B is locked.
+// So one of A and B is locked, and any digit z they share that isn't
+// x or y can be removed from every cell that sees all of their places
+// for z.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AlsXyWingPattern {
+    pub sets: [AlmostLockedSet; 2],
+    pub pivot: AlmostLockedSet,
+    pub links: [usize; 2],
+    pub eliminations: Vec<Candidate>,
+}
+
+impl fmt::Display for AlsXyWingPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is linked to {} by {} and to {} by {}, so {} can be removed",
+               self.pivot, self.sets[0], self.links[0], self.sets[1], self.links[1],
+               candidate_names(&self.eliminations))
+    }
+}
+
+impl Pattern for AlsXyWingPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+}
+
+pub fn find_als_xy_wings(board: &Board) -> Vec<AlsXyWingPattern> {
+    let sets = almost_locked_sets(board);
+    let mut result = Vec::new();
+
+    // The restricted commons between every pair of sets, by index.
+    let mut links = vec![Vec::new(); sets.len()];
+    for i in 0..sets.len() {
+        for j in i+1..sets.len() {
+            for digit in restricted_commons(board, &sets[i], &sets[j]) {
+                links[i].push((j, digit));
+                links[j].push((i, digit));
+            }
+        }
+    }
+
+    for (pivot, pivot_links) in links.iter().enumerate() {
+        for pair in combinations(pivot_links, 2) {
+            let ((i, x), (j, y)) = (pair[0], pair[1]);
+            if i >= j || x == y || sets[i].overlaps(&sets[j]) {
+                continue;
+            }
+
+            let (a, b) = (&sets[i], &sets[j]);
+            let both = a.cells.iter().chain(&b.cells).copied().collect::<Vec<usize>>();
+            let eliminations = a.digits
+                .iter()
+                .filter(|digit| **digit != x && **digit != y && b.contains(**digit))
+                .flat_map(|digit| seeing_all(board, &both, *digit))
+                .collect::<Vec<Candidate>>();
+
+            if !eliminations.is_empty() {
+                result.push(AlsXyWingPattern {
+                    sets: [a.clone(), b.clone()],
+                    pivot: sets[pivot].clone(),
+                    links: [x, y],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+pub struct AlsXz;
+
+impl Strategy for AlsXz {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(AlsXz {})
+    }
+
+    fn name(&self) -> String {
+        String::from("ALS-XZ")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_als_xz(board))
+    }
+}
+
+pub struct AlsXyWing;
+
+impl Strategy for AlsXyWing {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(AlsXyWing {})
+    }
+
+    fn name(&self) -> String {
+        String::from("ALS-XY-Wing")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_als_xy_wings(board))
+    }
+}
@@ -0,0 +1,108 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use std::fmt;
+
+// Bivalue universal grave plus one: every unsolved cell has two
+// possible digits except for one cell with three.  If that cell
+// weren't the digit it has three places for in each of its groups,
+// every group would have exactly two places for each digit, and the
+// board would have two solutions.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BugPattern {
+    pub cell: usize,
+    pub digit: usize,
+}
+
+impl fmt::Display for BugPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is the only cell with three digits, so it must be {}",
+               cell_name(self.cell), self.digit)
+    }
+}
+
+impl Pattern for BugPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        Vec::new()
+    }
+
+    fn placements(&self) -> Vec<Candidate> {
+        vec![(self.cell, self.digit)]
+    }
+}
+
+pub fn find_bug(board: &Board) -> Option<BugPattern> {
+    let unsolved = (0..81)
+        .filter(|idx| matches!(board.cells[*idx], Cell::Unsolved(_)))
+        .collect::<Vec<usize>>();
+
+    let mut extra = unsolved.iter().filter(|idx| board.cells[**idx].count() != 2);
+    let cell = *extra.next()?;
+    if extra.next().is_some() || board.cells[cell].count() != 3 {
+        return None;
+    }
+
+    let groups = Board::all_groups()
+        .into_iter()
+        .filter(|group| group.contains(&cell))
+        .collect::<Vec<_>>();
+
+    // Every group the cell is in has to agree on the digit.
+    let mut digits = board.cells[cell]
+        .digits()
+        .into_iter()
+        .filter(|digit| groups.iter().all(|group| {
+            group.iter().filter(|idx| unsolved.contains(idx) && board.cells[**idx].contains(*digit)).count() == 3
+        }))
+        .collect::<Vec<usize>>();
+
+    if digits.len() != 1 {
+        return None;
+    }
+    let digit = digits.remove(0);
+
+    // With that digit taken out of the cell, every digit has to have
+    // exactly two places, or none, in every group.  Otherwise the
+    // board isn't a grave at all, and the digit proves nothing.
+    for group in Board::all_groups() {
+        for other in 1..=9 {
+            let places = group
+                .iter()
+                .filter(|idx| unsolved.contains(idx)
+                        && board.cells[**idx].contains(other)
+                        && (**idx, other) != (cell, digit))
+                .count();
+
+            if places != 0 && places != 2 {
+                return None;
+            }
+        }
+    }
+
+    Some(BugPattern { cell, digit })
+}
+
+pub struct Bug;
+
+impl Strategy for Bug {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(Bug {})
+    }
+
+    fn name(&self) -> String {
+        String::from("BUG+1")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_bug(board))
+    }
+
+    fn requires_uniqueness(&self) -> bool {
+        true
+    }
+}
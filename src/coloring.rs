@@ -0,0 +1,248 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_names;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// Which coloring rule made a deduction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColoringRule {
+    // Two cells of the same color see each other, so that color is
+    // false everywhere.
+    Wrap,
+
+    // A cell sees both colors of a cluster, so it can't hold the
+    // digit.
+    Trap,
+
+    // A color of one cluster sees both colors of another cluster, so
+    // that color is false everywhere.
+    MultiWrap,
+
+    // A color of one cluster sees a color of another, so one of the
+    // two opposite colors is true, and any cell seeing both of those
+    // can't hold the digit.
+    MultiTrap,
+}
+
+// The cells of one cluster of conjugate pairs for a digit, split into
+// the two colors.  Exactly one color holds the digit in every cell.
+pub type Cluster = [Vec<usize>; 2];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ColoringPattern {
+    pub digit: usize,
+    pub rule: ColoringRule,
+    pub colors: Vec<Vec<usize>>,
+    pub eliminations: Vec<usize>,
+}
+
+impl fmt::Display for ColoringPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let colors = self.colors
+            .iter()
+            .map(|color| cell_names(color))
+            .collect::<Vec<_>>()
+            .join("] vs [");
+        write!(f, "{:?} on {} coloring [{}] removes it from {}",
+               self.rule, self.digit, colors,
+               cell_names(&self.eliminations))
+    }
+}
+
+impl Pattern for ColoringPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+// Split the cells joined by conjugate pairs (the only two places for
+// the digit in some group) into clusters, and color each cluster so
+// that the two ends of every pair have opposite colors.
+pub fn clusters(board: &Board, digit: usize) -> Vec<Cluster> {
+    let mut links = BTreeSet::new();
+    for group in Board::all_groups() {
+        let places = board.places(&group, digit);
+        if places.len() == 2 {
+            links.insert((places[0], places[1]));
+            links.insert((places[1], places[0]));
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut colored = BTreeSet::new();
+
+    for (start, _) in &links {
+        if colored.contains(start) {
+            continue;
+        }
+
+        let mut cluster: Cluster = [Vec::new(), Vec::new()];
+        let mut todo = vec![(*start, 0)];
+        colored.insert(*start);
+
+        while let Some((idx, color)) = todo.pop() {
+            cluster[color].push(idx);
+            for (_, next) in links.range((idx, 0)..(idx + 1, 0)) {
+                if colored.insert(*next) {
+                    todo.push((*next, 1 - color));
+                }
+            }
+        }
+
+        cluster[0].sort_unstable();
+        cluster[1].sort_unstable();
+        result.push(cluster);
+    }
+
+    result
+}
+
+fn sees_any(idx: usize, cells: &[usize]) -> bool {
+    cells.iter().any(|other| Board::sees(idx, *other))
+}
+
+fn sees_each_other(a: &[usize], b: &[usize]) -> bool {
+    a.iter().any(|idx| sees_any(*idx, b))
+}
+
+// Apply the single cluster rules to every cluster on the board.
+pub fn find_simple_coloring(board: &Board) -> Vec<ColoringPattern> {
+    let mut result = Vec::new();
+
+    for digit in 1..=9 {
+        for cluster in clusters(board, digit) {
+            for color in &cluster {
+                if color.iter().any(|idx| sees_any(*idx, color)) {
+                    result.push(ColoringPattern {
+                        digit,
+                        rule: ColoringRule::Wrap,
+                        colors: cluster.to_vec(),
+                        eliminations: color.clone(),
+                    });
+                }
+            }
+
+            let eliminations = (0..81)
+                .filter(|idx| board.cells[*idx].contains(digit)
+                        && !cluster.iter().any(|color| color.contains(idx))
+                        && cluster.iter().all(|color| sees_any(*idx, color)))
+                .collect::<Vec<usize>>();
+
+            if !eliminations.is_empty() {
+                result.push(ColoringPattern {
+                    digit,
+                    rule: ColoringRule::Trap,
+                    colors: cluster.to_vec(),
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+// Apply the rules joining two clusters to every pair of clusters on
+// the board.
+pub fn find_multi_coloring(board: &Board) -> Vec<ColoringPattern> {
+    let mut result = Vec::new();
+
+    for digit in 1..=9 {
+        let clusters = clusters(board, digit);
+
+        for (i, first) in clusters.iter().enumerate() {
+            for (j, second) in clusters.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                for a in 0..2 {
+                    // If this color sees both colors of the other
+                    // cluster, it can't be true.
+                    if sees_each_other(&first[a], &second[0]) && sees_each_other(&first[a], &second[1]) {
+                        result.push(ColoringPattern {
+                            digit,
+                            rule: ColoringRule::MultiWrap,
+                            colors: vec![first[a].clone(), second[0].clone(), second[1].clone()],
+                            eliminations: first[a].clone(),
+                        });
+                    }
+
+                    // Each unordered pair of clusters only needs
+                    // checking once for the trap.
+                    if i > j {
+                        continue;
+                    }
+
+                    for b in 0..2 {
+                        if !sees_each_other(&first[a], &second[b]) {
+                            continue;
+                        }
+
+                        // These two colors can't both be true, so one
+                        // of their opposites is.
+                        let (other_first, other_second) = (&first[1 - a], &second[1 - b]);
+                        let eliminations = (0..81)
+                            .filter(|idx| board.cells[*idx].contains(digit)
+                                    && !other_first.contains(idx)
+                                    && !other_second.contains(idx)
+                                    && sees_any(*idx, other_first)
+                                    && sees_any(*idx, other_second))
+                            .collect::<Vec<usize>>();
+
+                        if !eliminations.is_empty() {
+                            result.push(ColoringPattern {
+                                digit,
+                                rule: ColoringRule::MultiTrap,
+                                colors: vec![first[a].clone(), second[b].clone(), other_first.clone(), other_second.clone()],
+                                eliminations,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// Color each cluster of conjugate pairs on its own.
+pub struct SimpleColoring;
+
+impl Strategy for SimpleColoring {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(SimpleColoring {})
+    }
+
+    fn name(&self) -> String {
+        String::from("SimpleColoring")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_simple_coloring(board))
+    }
+}
+
+// Color clusters of conjugate pairs and look for ways that separate
+// clusters constrain each other.
+pub struct MultiColoring;
+
+impl Strategy for MultiColoring {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(MultiColoring {})
+    }
+
+    fn name(&self) -> String {
+        String::from("MultiColoring")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_multi_coloring(board))
+    }
+}
@@ -0,0 +1,153 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::als::{AlmostLockedSet, almost_locked_sets, seeing_all};
+use std::collections::BTreeSet;
+use std::fmt;
+
+// A stem cell with a petal for each of its digits: an almost locked
+// set that could be the digit, but only in cells that see the stem.
+// Whichever digit the stem turns out to be, that petal loses it and
+// is locked, so a digit every petal shares (and the stem doesn't) is
+// in at least one petal, and can be removed from every cell that sees
+// all of the petals' places for it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeathBlossomPattern {
+    pub stem: usize,
+    pub digit: usize,
+    pub petals: Vec<(usize, AlmostLockedSet)>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl fmt::Display for DeathBlossomPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} has petals {}, so {} is removed from {}",
+               cell_name(self.stem),
+               self.petals
+               .iter()
+               .map(|(digit, petal)| format!("{} on {}", digit, petal))
+               .collect::<Vec<_>>()
+               .join("; "),
+               self.digit,
+               self.eliminations.iter().map(|(idx, _)| cell_name(*idx)).collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl Pattern for DeathBlossomPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+}
+
+// Choose a petal for each remaining stem digit in turn, keeping track
+// of the cells that still see every place for the digit in the petals
+// chosen so far, and giving up on a branch once there are none.
+fn grow<'a>(board: &Board, digit: usize, petals: &[(usize, Vec<&'a AlmostLockedSet>)],
+            chosen: &mut Vec<(usize, &'a AlmostLockedSet)>, found: &mut Vec<Vec<(usize, &'a AlmostLockedSet)>>) {
+    let cells = chosen.iter().flat_map(|(_, petal)| petal.cells.iter().copied()).collect::<Vec<usize>>();
+    if !chosen.is_empty() && seeing_all(board, &cells, digit).is_empty() {
+        return;
+    }
+
+    let Some((stem_digit, options)) = petals.get(chosen.len()) else {
+        found.push(chosen.clone());
+        return;
+    };
+
+    for petal in options {
+        chosen.push((*stem_digit, petal));
+        grow(board, digit, petals, chosen, found);
+        chosen.pop();
+    }
+}
+
+// Find death blossoms whose petals have at most max_size cells.
+pub fn find_death_blossoms(board: &Board, max_size: usize) -> Vec<DeathBlossomPattern> {
+    let sets = almost_locked_sets(board)
+        .into_iter()
+        .filter(|set| set.cells.len() <= max_size)
+        .collect::<Vec<AlmostLockedSet>>();
+
+    let mut result = Vec::new();
+    let mut eliminated = BTreeSet::new();
+
+    for stem in (0..81).filter(|idx| matches!(board.cells[*idx], Cell::Unsolved(_))) {
+        let mut stem_digits = board.cells[stem].digits().into_iter().collect::<Vec<usize>>();
+        stem_digits.sort_unstable();
+
+        for digit in (1..=9).filter(|digit| !stem_digits.contains(digit)) {
+            let petals = stem_digits
+                .iter()
+                .map(|stem_digit| (*stem_digit, sets
+                                   .iter()
+                                   .filter(|set| !set.cells.contains(&stem)
+                                           && set.contains(*stem_digit)
+                                           && set.contains(digit)
+                                           && set.places(board, *stem_digit).iter().all(|idx| Board::sees(*idx, stem)))
+                                   .collect::<Vec<&AlmostLockedSet>>()))
+                .collect::<Vec<_>>();
+
+            let mut found = Vec::new();
+            grow(board, digit, &petals, &mut Vec::new(), &mut found);
+
+            for chosen in found {
+                let cells = chosen.iter().flat_map(|(_, petal)| petal.cells.iter().copied()).collect::<Vec<usize>>();
+                let eliminations = seeing_all(board, &cells, digit);
+
+                // Many different petals can make the same eliminations,
+                // so only keep the first that does.
+                if eliminations.iter().all(|candidate| eliminated.contains(candidate)) {
+                    continue;
+                }
+                eliminated.extend(eliminations.iter().copied());
+
+                result.push(DeathBlossomPattern {
+                    stem,
+                    digit,
+                    petals: chosen.into_iter().map(|(stem_digit, petal)| (stem_digit, petal.clone())).collect(),
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+// Death blossom.  Looking for petals means trying every combination
+// of almost locked sets around every cell, which gets slow quickly as
+// the sets get bigger, so it isn't registered with the other
+// strategies and petals are limited to max_size cells.  Use
+// Solver::death_blossom to turn it on.
+pub struct DeathBlossom {
+    max_size: usize,
+}
+
+impl DeathBlossom {
+    pub const DEFAULT_MAX_SIZE: usize = 3;
+
+    // Make a death blossom strategy that only uses petals of at most
+    // max_size cells.
+    pub fn with_max_size(max_size: usize) -> Box<dyn Strategy> {
+        Box::new(DeathBlossom { max_size })
+    }
+}
+
+impl Strategy for DeathBlossom {
+    fn new() -> Box<dyn Strategy> {
+        Self::with_max_size(Self::DEFAULT_MAX_SIZE)
+    }
+
+    fn name(&self) -> String {
+        String::from("DeathBlossom")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_death_blossoms(board, self.max_size))
+    }
+}
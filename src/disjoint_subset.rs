@@ -1,51 +1,77 @@
 use crate::Board;
+use crate::Contradiction;
 use crate::Strategy;
-use std::collections::HashMap;
+use crate::combinations;
+use std::collections::HashSet;
 
-pub struct NakedPair;
+// If N cells in a group can only hold N digits between them, those
+// digits must go in those cells, so they can be removed from every
+// other cell in the group.
+pub struct NakedSubset<const N: usize>;
 
-impl Strategy for NakedPair {
+pub type NakedPair = NakedSubset<2>;
+pub type NakedTriple = NakedSubset<3>;
+pub type NakedQuad = NakedSubset<4>;
+
+impl<const N: usize> Strategy for NakedSubset<N> {
     fn new() -> Box<dyn Strategy> {
-        Box::new(NakedPair {})
+        Box::new(NakedSubset::<N> {})
     }
 
     fn name(&self) -> String {
-        String::from("NakedPair")
+        match N {
+            2 => String::from("NakedPair"),
+            3 => String::from("NakedTriple"),
+            4 => String::from("NakedQuad"),
+            _ => format!("NakedSubset{}", N),
+        }
     }
 
-    fn apply(&self, board_in: &Board) -> Board {
+    fn deduce(&self, board_in: &Board) -> Result<(Board, Vec<String>), Contradiction> {
         let mut board = board_in.clone();
 
         for group in Board::all_groups() {
-            // Build up a set of all cells with two possible digits,
-            // and a count of the number of cells like that in this
-            // group that match that.
-            let mut naked_pair_counts = HashMap::new();
-            for cell_and_loc in board.get_cells(&group) {
-                naked_pair_counts.entry(cell_and_loc.cell.clone()).and_modify(|counter| *counter += 1).or_insert(1);
-            }
+            // Only unsolved cells with at most N possible digits can
+            // be part of a naked subset of size N.
+            let candidates = board
+                .get_cells(&group)
+                .into_iter()
+                .filter(|cell_and_loc| (2..=N).contains(&cell_and_loc.cell.count()))
+                .collect::<Vec<_>>();
+
+            for subset in combinations(&candidates, N) {
+                let digits = subset
+                    .iter()
+                    .flat_map(|cell_and_loc| cell_and_loc.cell.digits())
+                    .collect::<HashSet<usize>>();
+
+                // Fewer than N digits cannot fill N cells.
+                if digits.len() < N {
+                    return Err(Contradiction::new(&self.name()).with_group(&group));
+                }
+
+                if digits.len() > N {
+                    continue;
+                }
 
-            for (cell, count) in naked_pair_counts {
-                // If there are two of this pair, we know they must be
-                // the only cells that have these digits in this
-                // group.
-                if count == 2 {
-                    // The two digits in this pair can be removed from
-                    // all cells in this row that are not part of this
-                    // pair.
-                    let digits = cell.digits();
-                    assert!(digits.len() == 2);
-                    for board_idx in &group {
-                        let bcell = &mut board.cells[*board_idx];
-                        if *bcell != cell {
-                            digits.iter().for_each(|digit| board.cells[*board_idx].remove(*digit).unwrap());
+                // The digits in this subset can be removed from all
+                // cells in this group that are not part of it.
+                let subset_idxs = subset
+                    .iter()
+                    .map(|cell_and_loc| cell_and_loc.board_idx.unwrap())
+                    .collect::<Vec<_>>();
+
+                for board_idx in &group {
+                    if !subset_idxs.contains(board_idx) {
+                        for digit in &digits {
+                            board.remove(*board_idx, *digit, &self.name())
+                                .map_err(|c| c.with_group(&group))?;
                         }
                     }
                 }
             }
         }
 
-        board
+        Ok((board, Vec::new()))
     }
 }
-
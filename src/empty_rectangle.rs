@@ -0,0 +1,128 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use std::fmt;
+
+// A box where every place for a digit lies on one row or one column
+// of the box (leaving an empty rectangle of cells that can't hold
+// it), combined with a line outside the box that has only two places
+// for the digit, one of them on the box's row or column.
+//
+// For a column with places A and B, where A is on the box's row:
+// if the cell T on B's row and the box's column held the digit, B
+// couldn't, so A would have to, and then nowhere in the box could.
+// So T can't hold the digit.  Rows work the same way with the roles
+// swapped.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EmptyRectanglePattern {
+    pub digit: usize,
+    pub box_idx: usize,
+    pub row: usize,
+    pub column: usize,
+    pub link: [usize; 2],
+    pub elimination: usize,
+}
+
+impl fmt::Display for EmptyRectanglePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} in box {} is confined to row {} and column {}, and is at {} or {}, so it is removed from {}",
+               self.digit, self.box_idx, self.row, self.column,
+               cell_name(self.link[0]), cell_name(self.link[1]), cell_name(self.elimination))
+    }
+}
+
+impl Pattern for EmptyRectanglePattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        vec![(self.elimination, self.digit)]
+    }
+}
+
+pub fn find_empty_rectangles(board: &Board) -> Vec<EmptyRectanglePattern> {
+    let mut result = Vec::new();
+
+    for digit in 1..=9 {
+        for (box_idx, box_) in Board::boxes().iter().enumerate() {
+            let places = board.places(box_, digit);
+            if places.len() < 2 {
+                continue;
+            }
+
+            let box_rows = (0..9).filter(|row| box_.contains(&(row * 9 + box_[0] % 9))).collect::<Vec<_>>();
+            let box_columns = (0..9).filter(|column| box_.contains(&(box_[0] / 9 * 9 + column))).collect::<Vec<_>>();
+
+            for row in &box_rows {
+                for column in &box_columns {
+                    // Every place has to be on the row or column, but
+                    // not all on just one of them, since that is a
+                    // plain locked candidate.
+                    if !places.iter().all(|idx| idx / 9 == *row || idx % 9 == *column)
+                        || places.iter().all(|idx| idx / 9 == *row)
+                        || places.iter().all(|idx| idx % 9 == *column) {
+                        continue;
+                    }
+
+                    let mut found = Vec::new();
+
+                    for other_column in (0..9).filter(|c| !box_columns.contains(c)) {
+                        let link = board.places(&Board::columns()[other_column], digit);
+                        if link.len() != 2 {
+                            continue;
+                        }
+                        for (a, b) in [(link[0], link[1]), (link[1], link[0])] {
+                            if a / 9 == *row && !box_rows.contains(&(b / 9)) {
+                                found.push(([a, b], (b / 9) * 9 + column));
+                            }
+                        }
+                    }
+
+                    for other_row in (0..9).filter(|r| !box_rows.contains(r)) {
+                        let link = board.places(&Board::rows()[other_row], digit);
+                        if link.len() != 2 {
+                            continue;
+                        }
+                        for (a, b) in [(link[0], link[1]), (link[1], link[0])] {
+                            if a % 9 == *column && !box_columns.contains(&(b % 9)) {
+                                found.push(([a, b], row * 9 + b % 9));
+                            }
+                        }
+                    }
+
+                    for (link, elimination) in found {
+                        if board.cells[elimination].contains(digit) {
+                            result.push(EmptyRectanglePattern {
+                                digit,
+                                box_idx,
+                                row: *row,
+                                column: *column,
+                                link,
+                                elimination,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub struct EmptyRectangle;
+
+impl Strategy for EmptyRectangle {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(EmptyRectangle {})
+    }
+
+    fn name(&self) -> String {
+        String::from("EmptyRectangle")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_empty_rectangles(board))
+    }
+}
@@ -0,0 +1,310 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_names;
+use crate::combinations;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// A fish found on the board.  The places for the digit in each of the
+// base lines all fall on the cover lines, and since there are as many
+// base lines as cover lines, the digit can be removed from the rest
+// of the cover lines.  Lines are numbered 0-8; when row_based is set
+// the base lines are rows and the cover lines columns, otherwise the
+// other way around.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FishPattern {
+    pub digit: usize,
+    pub row_based: bool,
+    pub base: Vec<usize>,
+    pub cover: Vec<usize>,
+    pub eliminations: Vec<usize>,
+}
+
+impl fmt::Display for FishPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (base, cover) = if self.row_based { ("rows", "columns") } else { ("columns", "rows") };
+        write!(f, "{} in {} {:?} is confined to {} {:?}, so it is removed from {}",
+               self.digit, base, self.base, cover, self.cover,
+               cell_names(&self.eliminations))
+    }
+}
+
+impl Pattern for FishPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+// A list of rows or columns, each a list of cell indices.
+type Lines = Vec<Vec<usize>>;
+
+// The base lines and cover lines to search, for both orientations.
+// The nth cell of each base line is always on the nth cover line.
+fn orientations() -> [(bool, Lines, Lines); 2] {
+    [(true, Board::rows(), Board::columns()),
+     (false, Board::columns(), Board::rows())]
+}
+
+// For each base line that has not already solved the digit, the
+// cover lines the digit could be on.
+fn places(board: &Board, digit: usize, base_lines: &[Vec<usize>]) -> Vec<(usize, BTreeSet<usize>)> {
+    base_lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.iter().any(|idx| board.cells[*idx] == Cell::Solved(digit)))
+        .map(|(base, line)| (base, line
+                                   .iter()
+                                   .enumerate()
+                                   .filter(|(_, idx)| board.cells[**idx].contains(digit))
+                                   .map(|(cover, _)| cover)
+                                   .collect::<BTreeSet<usize>>()))
+        .collect()
+}
+
+// Find every fish with N base lines on the board that has something
+// to eliminate.
+pub fn find_fish(board: &Board, size: usize) -> Vec<FishPattern> {
+    let mut result = Vec::new();
+
+    for digit in 1..=9 {
+        for (row_based, base_lines, cover_lines) in orientations() {
+            let candidates = places(board, digit, &base_lines)
+                .into_iter()
+                .filter(|(_, covers)| !covers.is_empty() && covers.len() <= size)
+                .collect::<Vec<_>>();
+
+            for subset in combinations(&candidates, size) {
+                let cover = subset
+                    .iter()
+                    .flat_map(|(_, covers)| covers.iter().copied())
+                    .collect::<BTreeSet<usize>>();
+
+                if cover.len() != size {
+                    continue;
+                }
+
+                let base = subset.iter().map(|(base, _)| *base).collect::<Vec<usize>>();
+                let base_cells = base.iter().flat_map(|base| base_lines[*base].iter()).collect::<Vec<_>>();
+
+                let eliminations = cover
+                    .iter()
+                    .flat_map(|cover| cover_lines[*cover].iter())
+                    .filter(|idx| !base_cells.contains(idx) && board.cells[**idx].contains(digit))
+                    .copied()
+                    .collect::<Vec<usize>>();
+
+                if !eliminations.is_empty() {
+                    result.push(FishPattern {
+                        digit,
+                        row_based,
+                        base,
+                        cover: cover.into_iter().collect(),
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// A basic fish of size N: N rows where a digit is confined to N
+// columns, or N columns where it is confined to N rows.
+pub struct Fish<const N: usize>;
+
+pub type XWing = Fish<2>;
+pub type Swordfish = Fish<3>;
+pub type Jellyfish = Fish<4>;
+
+impl<const N: usize> Strategy for Fish<N> {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(Fish::<N> {})
+    }
+
+    fn name(&self) -> String {
+        match N {
+            2 => String::from("XWing"),
+            3 => String::from("Swordfish"),
+            4 => String::from("Jellyfish"),
+            _ => format!("Fish{}", N),
+        }
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_fish(board, N))
+    }
+}
+
+// A fish that only holds once some extra "fin" places for the digit
+// are ignored.  The fins all sit in one box, so either one of them
+// holds the digit or the fish does; in both cases the digit can be
+// removed from the cells of the cover lines in that box.  It is a
+// sashimi fish if one of its base lines has at most one place left on
+// the cover lines, ie the fish would be degenerate without its fins.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FinnedFishPattern {
+    pub fish: FishPattern,
+    pub fins: Vec<usize>,
+    pub sashimi: bool,
+}
+
+impl fmt::Display for FinnedFishPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (base, cover) = if self.fish.row_based { ("rows", "columns") } else { ("columns", "rows") };
+        write!(f, "{} in {} {:?} is confined to {} {:?} apart from fins at {}, so it is removed from {}",
+               self.fish.digit, base, self.fish.base, cover, self.fish.cover,
+               cell_names(&self.fins),
+               cell_names(&self.fish.eliminations))
+    }
+}
+
+impl Pattern for FinnedFishPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.fish.eliminations()
+    }
+}
+
+// Find every finned or sashimi fish with N base lines on the board
+// that has something to eliminate.
+pub fn find_finned_fish(board: &Board, size: usize) -> Vec<FinnedFishPattern> {
+    let mut result = Vec::new();
+    let boxes = Board::boxes();
+
+    for digit in 1..=9 {
+        for (row_based, base_lines, cover_lines) in orientations() {
+            let candidates = places(board, digit, &base_lines)
+                .into_iter()
+                .filter(|(_, covers)| !covers.is_empty())
+                .collect::<Vec<_>>();
+
+            for subset in combinations(&candidates, size) {
+                let base = subset.iter().map(|(base, _)| *base).collect::<Vec<usize>>();
+                let base_cells = base.iter().flat_map(|base| base_lines[*base].iter()).collect::<Vec<_>>();
+
+                for box_ in &boxes {
+                    // Every place outside the fin box has to be on a
+                    // cover line.
+                    let required = subset
+                        .iter()
+                        .flat_map(|(base, covers)| covers
+                                  .iter()
+                                  .filter(|cover| !box_.contains(&base_lines[*base][**cover]))
+                                  .copied())
+                        .collect::<BTreeSet<usize>>();
+
+                    if required.len() > size {
+                        continue;
+                    }
+
+                    // The rest of the cover lines only matter if they
+                    // go through the fin box, since that is the only
+                    // place anything can be eliminated.
+                    let through_box = (0..9)
+                        .filter(|cover| !required.contains(cover)
+                                && cover_lines[*cover].iter().any(|idx| box_.contains(idx)))
+                        .collect::<Vec<usize>>();
+
+                    let needed = (size - required.len()).min(through_box.len());
+                    for extra in combinations(&through_box, needed) {
+                        let mut cover = required.clone();
+                        cover.extend(extra);
+
+                        let fins = subset
+                            .iter()
+                            .flat_map(|(base, covers)| covers
+                                      .iter()
+                                      .filter(|c| !cover.contains(c))
+                                      .map(|c| base_lines[*base][*c]))
+                            .collect::<Vec<usize>>();
+
+                        // Without fins this is a basic fish.
+                        if fins.is_empty() {
+                            continue;
+                        }
+
+                        let eliminations = cover
+                            .iter()
+                            .flat_map(|cover| cover_lines[*cover].iter())
+                            .filter(|idx| box_.contains(idx)
+                                    && !base_cells.contains(idx)
+                                    && board.cells[**idx].contains(digit))
+                            .copied()
+                            .collect::<Vec<usize>>();
+
+                        if eliminations.is_empty() {
+                            continue;
+                        }
+
+                        let sashimi = subset
+                            .iter()
+                            .any(|(_, covers)| covers.iter().filter(|c| cover.contains(c)).count() <= 1);
+
+                        result.push(FinnedFishPattern {
+                            fish: FishPattern {
+                                digit,
+                                row_based,
+                                base: base.clone(),
+                                cover: cover.into_iter().collect(),
+                                eliminations,
+                            },
+                            fins,
+                            sashimi,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// A finned fish of size N whose base lines each still have at least
+// two places on the cover lines.
+pub struct FinnedFish<const N: usize>;
+
+pub type FinnedXWing = FinnedFish<2>;
+pub type FinnedSwordfish = FinnedFish<3>;
+pub type FinnedJellyfish = FinnedFish<4>;
+
+impl<const N: usize> Strategy for FinnedFish<N> {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(FinnedFish::<N> {})
+    }
+
+    fn name(&self) -> String {
+        format!("Finned{}", Fish::<N>.name())
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_finned_fish(board, N).into_iter().filter(|finned| !finned.sashimi))
+    }
+}
+
+// A finned fish of size N where at least one base line is left with
+// a single place on the cover lines once the fins are ignored.
+pub struct SashimiFish<const N: usize>;
+
+pub type SashimiXWing = SashimiFish<2>;
+pub type SashimiSwordfish = SashimiFish<3>;
+pub type SashimiJellyfish = SashimiFish<4>;
+
+impl<const N: usize> Strategy for SashimiFish<N> {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(SashimiFish::<N> {})
+    }
+
+    fn name(&self) -> String {
+        format!("Sashimi{}", Fish::<N>.name())
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_finned_fish(board, N).into_iter().filter(|finned| finned.sashimi))
+    }
+}
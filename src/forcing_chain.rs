@@ -0,0 +1,159 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::candidate_names;
+use crate::nishio::propagate;
+use crate::solver::SolveState;
+use std::fmt;
+
+// A set of candidates, exactly one of which must be true (the digits
+// of one cell, or the places for a digit in one group), that all lead
+// to the same conclusions once each is placed and propagated.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ForcingChainPattern {
+    pub branches: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+    pub placements: Vec<Candidate>,
+}
+
+impl fmt::Display for ForcingChainPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "whichever of {} is true", candidate_names(&self.branches))?;
+        if !self.placements.is_empty() {
+            write!(f, " places {}", candidate_names(&self.placements))?;
+        }
+        if !self.eliminations.is_empty() {
+            write!(f, " removes {}", candidate_names(&self.eliminations))?;
+        }
+        Ok(())
+    }
+}
+
+impl Pattern for ForcingChainPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+
+    fn placements(&self) -> Vec<Candidate> {
+        self.placements.clone()
+    }
+}
+
+// Place each branch in turn and propagate it, then keep whatever all
+// of the resulting boards agree on.  Branches that make the board
+// invalid are left to Nishio, so they spoil the whole pattern.
+fn force(board: &Board, branches: Vec<Candidate>) -> Option<ForcingChainPattern> {
+    let mut boards = Vec::new();
+    for (idx, digit) in &branches {
+        let mut trial = board.clone();
+        trial.cells[*idx] = Cell::Solved(*digit);
+
+        let solution = propagate(&trial);
+        if solution.state == SolveState::Invalid {
+            return None;
+        }
+        boards.push(solution.board);
+    }
+
+    let mut eliminations = Vec::new();
+    let mut placements = Vec::new();
+
+    for idx in 0..81 {
+        if !matches!(board.cells[idx], Cell::Unsolved(_)) {
+            continue;
+        }
+
+        if let Cell::Solved(digit) = boards[0].cells[idx] {
+            if boards.iter().all(|other| other.cells[idx] == Cell::Solved(digit)) {
+                placements.push((idx, digit));
+                continue;
+            }
+        }
+
+        let mut digits = board.cells[idx].digits().into_iter().collect::<Vec<usize>>();
+        digits.sort_unstable();
+        eliminations.extend(digits
+                            .into_iter()
+                            .filter(|digit| boards.iter().all(|other| !other.cells[idx].contains(*digit)))
+                            .map(|digit| (idx, digit)));
+    }
+
+    if eliminations.is_empty() && placements.is_empty() {
+        return None;
+    }
+
+    Some(ForcingChainPattern {
+        branches,
+        eliminations,
+        placements,
+    })
+}
+
+// Try each digit of every cell with only two.
+pub fn find_cell_forcing_chains(board: &Board) -> Vec<ForcingChainPattern> {
+    (0..81)
+        .filter(|idx| matches!(board.cells[*idx], Cell::Unsolved(_)) && board.cells[*idx].count() == 2)
+        .filter_map(|idx| {
+            let mut digits = board.cells[idx].digits().into_iter().collect::<Vec<usize>>();
+            digits.sort_unstable();
+            force(board, digits.into_iter().map(|digit| (idx, digit)).collect())
+        })
+        .collect()
+}
+
+// Try each place for every digit in every group that hasn't already
+// placed it.
+pub fn find_unit_forcing_chains(board: &Board) -> Vec<ForcingChainPattern> {
+    let mut result = Vec::new();
+
+    for group in Board::all_groups() {
+        for digit in 1..=9 {
+            let places = board.places(&group, digit);
+            if places.len() < 2 || places.iter().any(|idx| board.cells[*idx] == Cell::Solved(digit)) {
+                continue;
+            }
+
+            result.extend(force(board, places.into_iter().map(|idx| (idx, digit)).collect()));
+        }
+    }
+
+    result
+}
+
+// Follow both digits of a cell that can only be one of two.
+pub struct CellForcingChain;
+
+impl Strategy for CellForcingChain {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(CellForcingChain {})
+    }
+
+    fn name(&self) -> String {
+        String::from("CellForcingChain")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_cell_forcing_chains(board))
+    }
+}
+
+// Follow every place a digit could go in a group.
+pub struct UnitForcingChain;
+
+impl Strategy for UnitForcingChain {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(UnitForcingChain {})
+    }
+
+    fn name(&self) -> String {
+        String::from("UnitForcingChain")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_unit_forcing_chains(board))
+    }
+}
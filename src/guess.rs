@@ -0,0 +1,173 @@
+use crate::Board;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Strategy;
+use crate::cell_name;
+use crate::solver::{SolveState, Solver};
+use std::fmt;
+
+// One digit tried in a cell, how that turned out, and any further
+// guesses that were needed to get there.  A state of Stuck means the
+// guesses underneath ran out of depth before finding an answer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GuessNode {
+    pub cell: usize,
+    pub digit: usize,
+    pub state: SolveState,
+    pub guesses: Vec<GuessNode>,
+}
+
+impl fmt::Display for GuessNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "guessed {} at {}: {:?}", self.digit, cell_name(self.cell), self.state)
+    }
+}
+
+// Flatten a guess tree into lines, indenting each guess under the one
+// it was made after.
+pub fn guess_lines(guesses: &[GuessNode]) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut todo = guesses.iter().rev().map(|guess| (guess, 0)).collect::<Vec<_>>();
+
+    while let Some((guess, depth)) = todo.pop() {
+        result.push(format!("{}{}", "  ".repeat(depth), guess));
+        todo.extend(guess.guesses.iter().rev().map(|child| (child, depth + 1)));
+    }
+
+    result
+}
+
+// The result of a backtracking run: the solved board if one was
+// found, otherwise the board the logical strategies got stuck on,
+// along with every guess that was tried.
+#[derive(Clone, Debug)]
+pub struct BacktrackSolution {
+    pub board: Board,
+    pub state: SolveState,
+    pub guesses: Vec<GuessNode>,
+}
+
+// A solver that falls back on guessing when the logical strategies
+// get stuck.  It guesses in the cell with the fewest possible digits,
+// tries each of them on a copy of the board, and carries on solving
+// from there, guessing again if it has to, up to max_depth guesses
+// deep.
+pub struct Backtracker {
+    solver: Solver,
+    max_depth: usize,
+}
+
+impl Backtracker {
+    pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+    // Make a backtracker that runs the passed-in solver between
+    // guesses, with whatever strategies and options it was set up
+    // with.
+    pub fn with_solver(solver: Solver) -> Self {
+        Backtracker {
+            solver,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    // Limit how many guesses deep the search can go.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn solve(&self, board: &Board) -> BacktrackSolution {
+        self.search(board, 0)
+    }
+
+    fn search(&self, board: &Board, depth: usize) -> BacktrackSolution {
+        let solution = self.solver.solve(board);
+        if solution.state != SolveState::Stuck || depth == self.max_depth {
+            return BacktrackSolution { board: solution.board, state: solution.state, guesses: Vec::new() };
+        }
+
+        let cell = (0..81)
+            .filter(|idx| matches!(solution.board.cells[*idx], Cell::Unsolved(_)))
+            .min_by_key(|idx| solution.board.cells[*idx].count())
+            .unwrap();
+
+        let mut digits = solution.board.cells[cell].digits().into_iter().collect::<Vec<usize>>();
+        digits.sort_unstable();
+
+        let mut guesses = Vec::new();
+        let mut state = SolveState::Invalid;
+
+        for digit in digits {
+            let mut trial = solution.board.clone();
+            trial.cells[cell] = Cell::Solved(digit);
+
+            let result = self.search(&trial, depth + 1);
+            guesses.push(GuessNode {
+                cell,
+                digit,
+                state: result.state,
+                guesses: result.guesses,
+            });
+
+            match result.state {
+                SolveState::Solved => {
+                    return BacktrackSolution { board: result.board, state: SolveState::Solved, guesses };
+                },
+                SolveState::Stuck => state = SolveState::Stuck,
+                SolveState::Invalid => (),
+            }
+        }
+
+        // Nothing worked.  If every guess was invalid the board has
+        // no solution, otherwise it might have one deeper down.
+        BacktrackSolution { board: solution.board, state, guesses }
+    }
+}
+
+// When nothing logical is left, guess: hand the board to a
+// backtracker and place the first digit of the solution it finds.
+// The details describe every guess that was tried along the way.
+// This isn't a deduction, so it isn't one of the solver's default
+// strategies; add it to the end of a solver's strategies to have it
+// guess once everything else is stuck.
+pub struct Guess {
+    backtracker: Backtracker,
+}
+
+impl Guess {
+    // Make a guess strategy that searches with the passed-in
+    // backtracker, so it can use the same strategies and options as
+    // the solver it is added to.
+    pub fn with_backtracker(backtracker: Backtracker) -> Box<dyn Strategy> {
+        Box::new(Guess { backtracker })
+    }
+}
+
+impl Strategy for Guess {
+    fn new() -> Box<dyn Strategy> {
+        Self::with_backtracker(Backtracker::with_solver(Solver::new()))
+    }
+
+    fn name(&self) -> String {
+        String::from("Guess")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        let mut result = board.clone();
+        let solution = self.backtracker.solve(board);
+
+        match (solution.state, solution.guesses.last()) {
+            (SolveState::Solved, Some(guess)) => result.place(guess.cell, guess.digit, &self.name())?,
+
+            // Every digit in the guessed cell led to a contradiction.
+            (SolveState::Invalid, Some(guess)) => return Err(Contradiction::new(&self.name()).with_cell(guess.cell)),
+
+            // The board was invalid before there was anything to guess.
+            (SolveState::Invalid, None) => return Err(Contradiction::new(&self.name())),
+
+            _ => {},
+        }
+
+        Ok((result, guess_lines(&solution.guesses)))
+    }
+}
@@ -0,0 +1,45 @@
+use crate::Board;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Strategy;
+
+// If a digit can only go in one cell of a row, column or box, then
+// that cell must be that digit.
+pub struct HiddenSingle;
+
+impl Strategy for HiddenSingle {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(HiddenSingle {})
+    }
+
+    fn name(&self) -> String {
+        String::from("HiddenSingle")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        let mut result = board.clone();
+
+        for group in Board::all_groups() {
+            for digit in 1..=9 {
+                let places = group
+                    .iter()
+                    .filter(|idx| board.cells[**idx].contains(digit))
+                    .collect::<Vec<_>>();
+
+                match places[..] {
+                    // Every group needs every digit somewhere.
+                    [] => return Err(Contradiction::new(&self.name()).with_group(&group).with_digit(digit)),
+
+                    [idx] if !matches!(board.cells[*idx], Cell::Solved(_)) => {
+                        result.place(*idx, digit, &self.name())
+                            .map_err(|c| c.with_group(&group))?;
+                    },
+
+                    _ => {},
+                }
+            }
+        }
+
+        Ok((result, Vec::new()))
+    }
+}
@@ -0,0 +1,89 @@
+use crate::Board;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Strategy;
+use crate::combinations;
+use std::collections::HashSet;
+
+// If N digits can only go in the same N cells of a group, those cells
+// must hold those digits, so every other digit can be removed from
+// them.
+pub struct HiddenSubset<const N: usize>;
+
+pub type HiddenPair = HiddenSubset<2>;
+pub type HiddenTriple = HiddenSubset<3>;
+pub type HiddenQuad = HiddenSubset<4>;
+
+impl<const N: usize> Strategy for HiddenSubset<N> {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(HiddenSubset::<N> {})
+    }
+
+    fn name(&self) -> String {
+        match N {
+            2 => String::from("HiddenPair"),
+            3 => String::from("HiddenTriple"),
+            4 => String::from("HiddenQuad"),
+            _ => format!("HiddenSubset{}", N),
+        }
+    }
+
+    fn deduce(&self, board_in: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        let mut board = board_in.clone();
+
+        for group in Board::all_groups() {
+            let cells = board.get_cells(&group);
+
+            // Digits that are already solved in this group can't be
+            // part of a hidden subset.
+            let solved = cells
+                .iter()
+                .filter_map(|cell_and_loc| match cell_and_loc.cell {
+                    Cell::Solved(digit) => Some(digit),
+                    _ => None,
+                })
+                .collect::<HashSet<usize>>();
+
+            // For each remaining digit, the board indices of the
+            // cells in this group that could hold it.  A digit with
+            // more than N places can't be part of a subset of N.
+            let places = (1..=9)
+                .filter(|digit| !solved.contains(digit))
+                .map(|digit| (digit, cells
+                                     .iter()
+                                     .filter(|cell_and_loc| cell_and_loc.cell.contains(digit))
+                                     .map(|cell_and_loc| cell_and_loc.board_idx.unwrap())
+                                     .collect::<HashSet<usize>>()))
+                .filter(|(_, idxs)| idxs.len() <= N)
+                .collect::<Vec<_>>();
+
+            for subset in combinations(&places, N) {
+                let digits = subset.iter().map(|(digit, _)| *digit).collect::<Vec<usize>>();
+                let idxs = subset
+                    .iter()
+                    .flat_map(|(_, idxs)| idxs.iter().copied())
+                    .collect::<HashSet<usize>>();
+
+                // N digits cannot fit in fewer than N cells.
+                if idxs.len() < N {
+                    return Err(Contradiction::new(&self.name()).with_group(&group));
+                }
+
+                if idxs.len() > N {
+                    continue;
+                }
+
+                // Everything else can be removed from the cells in
+                // this subset.
+                for idx in idxs {
+                    for digit in (1..=9).filter(|digit| !digits.contains(digit)) {
+                        board.remove(idx, digit, &self.name())
+                            .map_err(|c| c.with_group(&group))?;
+                    }
+                }
+            }
+        }
+
+        Ok((board, Vec::new()))
+    }
+}
@@ -0,0 +1,74 @@
+use crate::Board;
+use crate::Contradiction;
+use crate::Strategy;
+
+// Where a box crosses a row or column, look for a digit that is
+// locked into the three shared cells from one side:
+//
+// - If every place for the digit in the box is on the line, it must
+//   be on the line inside this box, so it can be removed from the
+//   rest of the line (pointing).
+//
+// - If every place for the digit on the line is in the box, it can
+//   likewise be removed from the rest of the box (box/line reduction).
+pub struct LockedCandidates;
+
+impl LockedCandidates {
+    // Remove digit from every cell in group that is not in keep, as
+    // long as every place the digit could go in from is in keep.
+    fn lock(&self, board: &mut Board, digit: usize, from: &[usize], keep: &[usize], group: &[usize]) -> Result<(), Contradiction> {
+        let places = from
+            .iter()
+            .filter(|idx| board.cells[**idx].contains(digit))
+            .collect::<Vec<_>>();
+
+        if places.is_empty() || !places.iter().all(|idx| keep.contains(idx)) {
+            return Ok(());
+        }
+
+        for idx in group.iter().filter(|idx| !keep.contains(idx)) {
+            board.remove(*idx, digit, &self.name())
+                .map_err(|c| c.with_group(group))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Strategy for LockedCandidates {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(LockedCandidates {})
+    }
+
+    fn name(&self) -> String {
+        String::from("LockedCandidates")
+    }
+
+    fn deduce(&self, board_in: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        let mut board = board_in.clone();
+
+        let mut lines = Board::rows();
+        lines.extend(Board::columns());
+
+        for box_ in Board::boxes() {
+            for line in &lines {
+                let shared = box_
+                    .iter()
+                    .filter(|idx| line.contains(idx))
+                    .copied()
+                    .collect::<Vec<usize>>();
+
+                if shared.is_empty() {
+                    continue;
+                }
+
+                for digit in 1..=9 {
+                    self.lock(&mut board, digit, &box_, &shared, line)?;
+                    self.lock(&mut board, digit, line, &shared, &box_)?;
+                }
+            }
+        }
+
+        Ok((board, Vec::new()))
+    }
+}
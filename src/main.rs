@@ -1,29 +1,82 @@
 use colored::Colorize;
 use fixedbitset::FixedBitSet;
 use std::collections::HashSet;
+use std::env;
+use std::fmt;
 use std::str::FromStr;
 
 mod remove_solved;
+mod aic;
+mod als;
+mod bug;
+mod coloring;
+mod death_blossom;
 mod disjoint_subset;
+mod empty_rectangle;
+mod fish;
+mod forcing_chain;
+mod guess;
+mod hidden_single;
+mod hidden_subset;
+mod locked_candidates;
+mod medusa;
+mod nishio;
+mod skyscraper;
+mod solver;
+mod sue_de_coq;
+mod two_string_kite;
+mod unique_rectangle;
+mod w_wing;
+mod x_cycle;
+mod xy_chain;
+mod xy_wing;
 
 use remove_solved::RemoveSolvedFromNeighbors;
-use disjoint_subset::NakedPair;
+use aic::Aic;
+use als::{AlsXz, AlsXyWing};
+use bug::Bug;
+use coloring::{SimpleColoring, MultiColoring};
+use death_blossom::DeathBlossom;
+use disjoint_subset::{NakedPair, NakedTriple, NakedQuad};
+use empty_rectangle::EmptyRectangle;
+use fish::{XWing, Swordfish, Jellyfish};
+use fish::{FinnedXWing, FinnedSwordfish, FinnedJellyfish};
+use fish::{SashimiXWing, SashimiSwordfish, SashimiJellyfish};
+use forcing_chain::{CellForcingChain, UnitForcingChain};
+use guess::{Backtracker, Guess};
+use hidden_single::HiddenSingle;
+use hidden_subset::{HiddenPair, HiddenTriple, HiddenQuad};
+use locked_candidates::LockedCandidates;
+use medusa::Medusa;
+use nishio::Nishio;
+use skyscraper::Skyscraper;
+use solver::Solver;
+use sue_de_coq::SueDeCoq;
+use two_string_kite::TwoStringKite;
+use unique_rectangle::{UniqueRectangle, HiddenUniqueRectangle};
+use w_wing::WWing;
+use x_cycle::XCycle;
+use xy_chain::XYChain;
+use xy_wing::{XYWing, XYZWing};
 
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod tests;
 
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
 enum Cell {
     Solved(usize),
     Unsolved(FixedBitSet),
+
+    // A cell with no possible digits left.  A board holding one of
+    // these cannot be solved.
+    Dead,
 }
 
 impl Cell {
     // Make a new empty cell that can hold any digit
     fn new() -> Self {
-        let mut bitset = FixedBitSet::with_capacity(9);
-        bitset.set_range(.., true);
-        Self::Unsolved(bitset)
+        Self::from_digits(1..=9)
     }
 
     // Make a new empty cell that can hold the digits in the passed-in
@@ -34,42 +87,59 @@ impl Cell {
     {
         let mut bitset = FixedBitSet::with_capacity(9);
         for digit in digits {
-            assert!(digit >= 1 && digit <= 9);
+            assert!((1..=9).contains(&digit));
             bitset.set(digit-1, true);
         }
 
-        if bitset.count_ones(..) == 1 {
+        match bitset.count_ones(..) {
+            // No possible values - this cell can never be solved
+            0 => Self::Dead,
+
             // Only one possible value - this is a solved cell
-            let digit = bitset.ones().next().unwrap() + 1;
-            Self::Solved(digit)
-        } else {
-            Self::Unsolved(bitset)
+            1 => Self::Solved(bitset.ones().next().unwrap() + 1),
+
+            _ => Self::Unsolved(bitset),
         }
     }
 
     // Remove the digit from the set of possible digits this cell can
     // hold.  If the cell is now down to just one possible digit,
-    // transition it to solved.
+    // transition it to solved.  If it has no possible digits left,
+    // transition it to dead and return an error.
     fn remove(&mut self, digit: usize) -> Result<(), String> {
-        if digit < 1 || digit > 9 {
+        if !(1..=9).contains(&digit) {
             return Err(format!("Cell::remove called with invalid digit {}", digit));
         }
 
         match self {
             Self::Unsolved(ref mut bitset) => {
                 bitset.set(digit-1, false);
-                if bitset.count_ones(..) == 1 {
-                    let digit = bitset.ones().next().unwrap() + 1;
-                    *self = Self::Solved(digit);
+                match bitset.count_ones(..) {
+                    0 => {
+                        *self = Self::Dead;
+                        Err(format!("Cell::remove removed last possible digit {}", digit))
+                    },
+                    1 => {
+                        let digit = bitset.ones().next().unwrap() + 1;
+                        *self = Self::Solved(digit);
+                        Ok(())
+                    },
+                    _ => Ok(()),
                 }
-                Ok(())
             },
 
-            // If some strategy is trying to remove a solved digit, that is an error.
-            Self::Solved(d) if *d == digit => Err(format!("Cell::remove asked to remove solved digit {}", digit)),
+            // If some strategy is trying to remove a solved digit,
+            // that is an error: the cell has nothing left it can be.
+            Self::Solved(d) if *d == digit => {
+                *self = Self::Dead;
+                Err(format!("Cell::remove asked to remove solved digit {}", digit))
+            },
 
             // On the other hand, trying to remove any digit other than the currently solved one is OK.
             Self::Solved(_) => Ok(()),
+
+            // There is nothing left to remove from a dead cell.
+            Self::Dead => Ok(()),
         }
     }
 
@@ -85,14 +155,17 @@ impl Cell {
                     if bits[idx] {
                         result.push(format!("{}", idx+1).dimmed());
                     } else {
-                        result.push(format!(" ").into());
+                        result.push(" ".into());
                     }
                 }
 
                 [format!("{}{}{}", result[0], result[1], result[2]),
                  format!("{}{}{}", result[3], result[4], result[5]),
                  format!("{}{}{}", result[6], result[7], result[8])]
-            }
+            },
+            Self::Dead => [String::from("   "),
+                           format!(" {} ", "X".red()),
+                           String::from("   ")],
         }
     }
 
@@ -101,6 +174,16 @@ impl Cell {
         match self {
             Self::Solved(_) => 1,
             Self::Unsolved(bitset) => bitset.count_ones(..),
+            Self::Dead => 0,
+        }
+    }
+
+    // Whether this cell could be the digit
+    fn contains(&self, digit: usize) -> bool {
+        match *self {
+            Self::Solved(val) => val == digit,
+            Self::Unsolved(ref bitset) => (1..=9).contains(&digit) && bitset[digit-1],
+            Self::Dead => false,
         }
     }
 
@@ -109,6 +192,7 @@ impl Cell {
         match *self {
             Self::Solved(val) => [val].into(),
             Self::Unsolved(ref bitset) => bitset.ones().map(|v| v+1).collect::<HashSet<usize>>(),
+            Self::Dead => HashSet::new(),
         }
     }
 }
@@ -155,6 +239,8 @@ struct Board {
 }
 
 impl Board {
+    // Make a new board where every cell can hold any digit.
+    #[cfg(test)]
     fn new() -> Self {
         Board {
             cells: core::array::from_fn(|_| Cell::new()),
@@ -228,7 +314,7 @@ impl Board {
                 let s = (lineno/4) * 9; // first cell in this line of output
                 let r = lineno%4; // row in each cell in this line of output
                 results.push(format!("{}|{}|{}||{}|{}|{}||{}|{}|{}",
-                                     cell_strs[s+0][r], cell_strs[s+1][r], cell_strs[s+2][r],
+                                     cell_strs[s][r], cell_strs[s+1][r], cell_strs[s+2][r],
                                      cell_strs[s+3][r], cell_strs[s+4][r], cell_strs[s+5][r],
                                      cell_strs[s+6][r], cell_strs[s+7][r], cell_strs[s+8][r]));
             }
@@ -240,8 +326,13 @@ impl Board {
     // Returns true if this board is valid, false otherwise.  Valid
     // means that it does not violate the basic sudoko constraints of
     // solved cells having a duplicate (solved) digit in the rest of
-    // that cell's row, column, and box neighbors.
+    // that cell's row, column, and box neighbors, and that no cell
+    // has been left without any possible digits.
     fn valid(&self) -> bool {
+        if !self.dead_cells().is_empty() {
+            return false;
+        }
+
         // This checks each pair of cells twice and could be
         // optimized.
         for idx in 0..81 {
@@ -261,15 +352,19 @@ impl Board {
         true
     }
 
+    // Return the indices of all cells that have no possible digits
+    // left.
+    fn dead_cells(&self) -> Vec<usize> {
+        (0..81)
+            .filter(|idx| self.cells[*idx] == Cell::Dead)
+            .collect()
+    }
+
     fn solved(&self) -> bool {
         self.valid()
             && self.cells
                    .iter()
-                   .all(|cell|
-                        match cell {
-                            Cell::Solved(_) => true,
-                            _ => false,
-                        })
+                   .all(|cell| matches!(cell, Cell::Solved(_)))
     }
 
     // Return all possible rows: a vector of rows, where a row is a
@@ -322,8 +417,8 @@ impl Board {
 
     fn all_groups() -> Vec<Vec<usize>> {
         let mut result = Self::rows();
-        result.extend(Self::columns().into_iter());
-        result.extend(Self::boxes().into_iter());
+        result.extend(Self::columns());
+        result.extend(Self::boxes());
         result
     }
 
@@ -395,6 +490,31 @@ impl Board {
         result
     }
 
+    // Returns true if the two cells are different cells that share a
+    // row, column or box.
+    fn sees(a: usize, b: usize) -> bool {
+        a != b
+            && (a / 9 == b / 9
+                || a % 9 == b % 9
+                || Self::box_of(a) == Self::box_of(b))
+    }
+
+    // Return the index of the box holding this cell, in the same
+    // order as boxes().
+    fn box_of(idx: usize) -> usize {
+        (idx / 27) * 3 + (idx % 9) / 3
+    }
+
+    // Return the indices of the cells in the group that could hold
+    // the digit.
+    fn places(&self, group: &[usize], digit: usize) -> Vec<usize> {
+        group
+            .iter()
+            .filter(|idx| self.cells[**idx].contains(digit))
+            .copied()
+            .collect()
+    }
+
     fn get_cells<'a, I>(&self, group: &'a I) -> HashSet<CellAndLoc>
     where
         &'a I: IntoIterator<Item = &'a usize>
@@ -408,6 +528,27 @@ impl Board {
                  .with_board_idx(*idx))
             .collect::<HashSet<CellAndLoc>>()
     }
+
+    // Remove the digit from the possible digits of the cell at idx.
+    // If that is not possible, or leaves the cell dead, report it as
+    // a contradiction found by the named strategy.
+    fn remove(&mut self, idx: usize, digit: usize, strategy: &str) -> Result<(), Contradiction> {
+        self.cells[idx]
+            .remove(digit)
+            .map_err(|_| Contradiction::new(strategy).with_cell(idx).with_digit(digit))
+    }
+
+    // Solve the cell at idx with the digit.  If the cell cannot hold
+    // that digit, report it as a contradiction found by the named
+    // strategy.
+    fn place(&mut self, idx: usize, digit: usize, strategy: &str) -> Result<(), Contradiction> {
+        if !self.cells[idx].contains(digit) {
+            return Err(Contradiction::new(strategy).with_cell(idx).with_digit(digit));
+        }
+
+        self.cells[idx] = Cell::Solved(digit);
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -427,36 +568,267 @@ impl FromStr for Board {
     }
 }
 
+// Evidence that a board cannot be solved, found by a strategy.  It
+// records which strategy found it and, where known, the cell, the
+// group (row, column or box as a list of cell indices) and the digit
+// involved.
+#[derive(Clone,PartialEq,Eq,Debug)]
+struct Contradiction {
+    strategy: String,
+    cell: Option<usize>,
+    group: Option<Vec<usize>>,
+    digit: Option<usize>,
+}
+
+impl Contradiction {
+    fn new(strategy: &str) -> Self {
+        Contradiction {
+            strategy: String::from(strategy),
+            cell: None,
+            group: None,
+            digit: None,
+        }
+    }
+
+    fn with_cell(mut self, idx: usize) -> Self {
+        self.cell = Some(idx);
+        self
+    }
+
+    fn with_group(mut self, group: &[usize]) -> Self {
+        self.group = Some(group.to_vec());
+        self
+    }
+
+    fn with_digit(mut self, digit: usize) -> Self {
+        self.digit = Some(digit);
+        self
+    }
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} found a contradiction", self.strategy)?;
+        if let Some(digit) = self.digit {
+            write!(f, " on digit {}", digit)?;
+        }
+        if let Some(idx) = self.cell {
+            write!(f, " at {}", cell_name(idx))?;
+        }
+        if let Some(ref group) = self.group {
+            write!(f, " in group {:?}", group)?;
+        }
+        Ok(())
+    }
+}
+
 trait Strategy {
     // Create a boxed instance
+    #[allow(clippy::new_ret_no_self)]
     fn new() -> Box<dyn Strategy> where Self: Sized;
 
     // The name of this strategy
     fn name(&self) -> String;
 
     // Apply the strategy to the input board, and return a new board
-    // that has had the strategy applied.
-    fn apply(&self, board: &Board) -> Board;
+    // that has had the strategy applied along with a description of
+    // each deduction that changed it, for the solver's step log.  If
+    // the board turns out to have no solution, return the
+    // contradiction found instead.  Strategies that don't explain
+    // themselves describe nothing.
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction>;
+
+    // Just the new board from deduce().
+    #[cfg(test)]
+    fn apply(&self, board: &Board) -> Result<Board, Contradiction> {
+        self.deduce(board).map(|(board, _)| board)
+    }
+
+    // Just the descriptions from deduce(), or nothing if the board
+    // has no solution.
+    #[cfg(test)]
+    fn explain(&self, board: &Board) -> Vec<String> {
+        self.deduce(board).map(|(_, details)| details).unwrap_or_default()
+    }
+
+    // Whether this strategy is only correct for puzzles with a single
+    // solution.  The solver skips these unless it has been told it can
+    // assume the puzzle has one.
+    fn requires_uniqueness(&self) -> bool {
+        false
+    }
+}
+
+// A deduction a strategy found on a board: the candidates it rules
+// out and the ones it places.  Its Display is the description that
+// goes in the solver's step log.
+trait Pattern: fmt::Display {
+    fn eliminations(&self) -> Vec<Candidate>;
+
+    fn placements(&self) -> Vec<Candidate> {
+        Vec::new()
+    }
+}
+
+// Apply each pattern's eliminations and placements to a copy of the
+// board in turn, for the strategy with the passed-in name.  Only the
+// patterns that still changed something by the time they were
+// applied are described.
+fn apply_patterns<P: Pattern>(name: &str, board_in: &Board, patterns: impl IntoIterator<Item = P>) -> Result<(Board, Vec<String>), Contradiction> {
+    let mut board = board_in.clone();
+    let mut details = Vec::new();
+
+    for pattern in patterns {
+        let mut changed = false;
+
+        for (idx, digit) in pattern.eliminations() {
+            changed |= board.cells[idx].contains(digit);
+            board.remove(idx, digit, name)?;
+        }
+        for (idx, digit) in pattern.placements() {
+            changed |= board.cells[idx] != Cell::Solved(digit);
+            board.place(idx, digit, name)?;
+        }
+
+        if changed {
+            details.push(pattern.to_string());
+        }
+    }
+
+    Ok((board, details))
 }
 
 fn get_strategies() -> Vec<Box<dyn Strategy>> {
     vec![
         RemoveSolvedFromNeighbors::new(),
+        HiddenSingle::new(),
         NakedPair::new(),
+        HiddenPair::new(),
+        NakedTriple::new(),
+        HiddenTriple::new(),
+        NakedQuad::new(),
+        HiddenQuad::new(),
+        LockedCandidates::new(),
+        XWing::new(),
+        Swordfish::new(),
+        Jellyfish::new(),
+        FinnedXWing::new(),
+        SashimiXWing::new(),
+        FinnedSwordfish::new(),
+        SashimiSwordfish::new(),
+        FinnedJellyfish::new(),
+        SashimiJellyfish::new(),
+        Skyscraper::new(),
+        TwoStringKite::new(),
+        EmptyRectangle::new(),
+        XYWing::new(),
+        XYZWing::new(),
+        WWing::new(),
+        UniqueRectangle::new(),
+        HiddenUniqueRectangle::new(),
+        Bug::new(),
+        SimpleColoring::new(),
+        MultiColoring::new(),
+        Medusa::new(),
+        XCycle::new(),
+        XYChain::new(),
+        Aic::new(),
+        SueDeCoq::new(),
+        AlsXz::new(),
+        AlsXyWing::new(),
+        CellForcingChain::new(),
+        UnitForcingChain::new(),
+        Nishio::new(),
     ]
 }
 
+// A candidate is a (cell index, digit) pair.
+type Candidate = (usize, usize);
+
+// The name of a cell for use in explanations, eg "row7 col1".
+fn cell_name(idx: usize) -> String {
+    format!("row{} col{}", idx / 9, idx % 9)
+}
+
+// The names of a list of cells, eg "row7 col1, row7 col4".
+fn cell_names(cells: &[usize]) -> String {
+    cells.iter().map(|idx| cell_name(*idx)).collect::<Vec<_>>().join(", ")
+}
+
+// The names of a list of candidates, eg "3 at row7 col1, 5 at row0 col2".
+fn candidate_names(candidates: &[Candidate]) -> String {
+    candidates
+        .iter()
+        .map(|(idx, digit)| format!("{} at {}", digit, cell_name(*idx)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// A list of digits run together, eg "1238".
+fn digit_names(digits: &[usize]) -> String {
+    digits.iter().map(|digit| digit.to_string()).collect::<Vec<_>>().join("")
+}
+
+// Return every way of choosing size items from the passed-in slice,
+// keeping the items in their original order.
+fn combinations<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[idx+1..], size-1) {
+            rest.insert(0, item.clone());
+            result.push(rest);
+        }
+    }
+
+    result
+}
+
 fn main() {
+    // The only argument, if there is one, is how many guesses deep the
+    // solver can go once the strategies get stuck.
+    let max_depth = match env::args().nth(1) {
+        Some(arg) => arg.parse().expect("the maximum guess depth should be a number"),
+        None => Backtracker::DEFAULT_MAX_DEPTH,
+    };
+
     let board = Board::from_str("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8...9..36..").unwrap();
     println!("Loaded board:");
     for str in board.to_strs() {
         println!("{}", str);
     }
 
-    let ob = RemoveSolvedFromNeighbors::new().apply(&board);
+    // Puzzles are meant to have a single solution, so the uniqueness
+    // strategies are fair game.  Guessing comes last, backtracking
+    // with the same strategies and options as everything before it.
+    let options = |solver: Solver| solver
+        .assume_unique(true)
+        .death_blossom(DeathBlossom::DEFAULT_MAX_SIZE);
+    let mut strategies = get_strategies();
+    strategies.push(Guess::with_backtracker(Backtracker::with_solver(options(Solver::new())).with_max_depth(max_depth)));
+    let solution = options(Solver::with_strategies(strategies)).solve(&board);
+
+    println!("Applied strategies:");
+    for step in &solution.steps {
+        println!("  {}", step.strategy);
+        for detail in &step.details {
+            println!("    {}", detail);
+        }
+    }
 
-    println!("After simple strategy:");
-    for str in ob.to_strs() {
+    let remaining: usize = solution.board.cells
+        .iter()
+        .filter(|c| matches!(c, Cell::Unsolved(_)))
+        .map(|c| c.count())
+        .sum();
+    println!("Final board ({:?}, {} candidates remaining):", solution.state, remaining);
+    if let Some(contradiction) = &solution.contradiction {
+        println!("{}", contradiction);
+    }
+    for str in solution.board.to_strs() {
         println!("{}", str);
     }
 }
@@ -0,0 +1,209 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::candidate_names;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// One deduction made by 3D Medusa, and which of the six rules made
+// it:
+//
+// 1. Two candidates of one color in the same cell: that color is
+//    false.
+// 2. The same digit twice in one color in a group: that color is
+//    false.
+// 3. A cell with candidates of both colors: every other candidate in
+//    the cell is false.
+// 4. An uncolored candidate that sees the same digit in both colors
+//    is false.
+// 5. An uncolored candidate in a cell holding one color that sees
+//    the same digit in the other color is false.
+// 6. A cell whose candidates are all uncolored and would all be
+//    removed by one color: that color is false.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MedusaPattern {
+    pub rule: usize,
+    pub colors: [Vec<Candidate>; 2],
+    pub eliminations: Vec<Candidate>,
+}
+
+impl fmt::Display for MedusaPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rule {} on [{}] vs [{}] removes {}",
+               self.rule, candidate_names(&self.colors[0]), candidate_names(&self.colors[1]),
+               candidate_names(&self.eliminations))
+    }
+}
+
+impl Pattern for MedusaPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+}
+
+// Join candidates by their strong links: the two digits of a cell
+// with only two possible digits, and the two places for a digit in a
+// group where it only has two.  Then split them into clusters,
+// coloring each so the ends of every link have opposite colors.
+pub fn medusa_clusters(board: &Board) -> Vec<[Vec<Candidate>; 2]> {
+    let mut links = BTreeSet::new();
+
+    for idx in 0..81 {
+        if matches!(board.cells[idx], Cell::Unsolved(_)) && board.cells[idx].count() == 2 {
+            let mut digits = board.cells[idx].digits().into_iter().collect::<Vec<usize>>();
+            digits.sort_unstable();
+            links.insert(((idx, digits[0]), (idx, digits[1])));
+            links.insert(((idx, digits[1]), (idx, digits[0])));
+        }
+    }
+
+    for group in Board::all_groups() {
+        for digit in 1..=9 {
+            let places = board.places(&group, digit);
+            if places.len() == 2 {
+                links.insert(((places[0], digit), (places[1], digit)));
+                links.insert(((places[1], digit), (places[0], digit)));
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut colored = BTreeSet::new();
+
+    for (start, _) in &links {
+        if colored.contains(start) {
+            continue;
+        }
+
+        let mut cluster: [Vec<Candidate>; 2] = [Vec::new(), Vec::new()];
+        let mut todo = vec![(*start, 0)];
+        colored.insert(*start);
+
+        while let Some((candidate, color)) = todo.pop() {
+            cluster[color].push(candidate);
+            for (_, next) in links.range((candidate, (0, 0))..((candidate.0, candidate.1 + 1), (0, 0))) {
+                if colored.insert(*next) {
+                    todo.push((*next, 1 - color));
+                }
+            }
+        }
+
+        cluster[0].sort_unstable();
+        cluster[1].sort_unstable();
+        result.push(cluster);
+    }
+
+    result
+}
+
+// Whether the candidate sees the same digit somewhere in the color.
+fn sees_digit(candidate: Candidate, color: &[Candidate]) -> bool {
+    color.iter().any(|(idx, digit)| *digit == candidate.1 && Board::sees(*idx, candidate.0))
+}
+
+pub fn find_medusa(board: &Board) -> Vec<MedusaPattern> {
+    let mut result = Vec::new();
+
+    for colors in medusa_clusters(board) {
+        let is_colored = |candidate: &Candidate| colors.iter().any(|color| color.contains(candidate));
+
+        for (x, color) in colors.iter().enumerate() {
+            let twice_in_cell = color
+                .iter()
+                .any(|(idx, digit)| color.iter().any(|(other, other_digit)| other == idx && other_digit != digit));
+
+            let twice_in_group = color
+                .iter()
+                .any(|candidate| sees_digit(*candidate, color));
+
+            let empties_cell = (0..81).any(|idx| {
+                let digits = board.cells[idx].digits();
+                matches!(board.cells[idx], Cell::Unsolved(_))
+                    && digits.iter().all(|digit| !is_colored(&(idx, *digit)))
+                    && digits.iter().all(|digit| sees_digit((idx, *digit), color))
+            });
+
+            for (rule, fired) in [(1, twice_in_cell), (2, twice_in_group), (6, empties_cell)] {
+                if fired {
+                    result.push(MedusaPattern {
+                        rule,
+                        colors: colors.clone(),
+                        eliminations: color.clone(),
+                    });
+                }
+            }
+
+            // Rule 5 works from each color in turn.
+            let eliminations = (0..81)
+                .filter(|idx| color.iter().any(|(other, _)| other == idx))
+                .flat_map(|idx| board.cells[idx].digits().into_iter().map(move |digit| (idx, digit)))
+                .filter(|candidate| !is_colored(candidate) && sees_digit(*candidate, &colors[1 - x]))
+                .collect::<BTreeSet<Candidate>>();
+
+            if !eliminations.is_empty() {
+                result.push(MedusaPattern {
+                    rule: 5,
+                    colors: colors.clone(),
+                    eliminations: eliminations.into_iter().collect(),
+                });
+            }
+        }
+
+        // Rule 3: one of the two colors is true in this cell, so
+        // nothing uncolored can be.
+        let eliminations = (0..81)
+            .filter(|idx| colors.iter().all(|color| color.iter().any(|(other, _)| other == idx)))
+            .flat_map(|idx| board.cells[idx].digits().into_iter().map(move |digit| (idx, digit)))
+            .filter(|candidate| !is_colored(candidate))
+            .collect::<BTreeSet<Candidate>>();
+
+        if !eliminations.is_empty() {
+            result.push(MedusaPattern {
+                rule: 3,
+                colors: colors.clone(),
+                eliminations: eliminations.into_iter().collect(),
+            });
+        }
+
+        // Rule 4: one of the two colors is true, so anything that sees
+        // the digit in both can't be it.
+        let eliminations = (0..81)
+            .flat_map(|idx| board.cells[idx].digits().into_iter().map(move |digit| (idx, digit)))
+            .filter(|candidate| !is_colored(candidate)
+                    && sees_digit(*candidate, &colors[0])
+                    && sees_digit(*candidate, &colors[1]))
+            .collect::<Vec<Candidate>>();
+
+        if !eliminations.is_empty() {
+            result.push(MedusaPattern {
+                rule: 4,
+                colors: colors.clone(),
+                eliminations,
+            });
+        }
+    }
+
+    result
+}
+
+// 3D Medusa coloring: like simple coloring, but following strong
+// links across digits as well as between cells.
+pub struct Medusa;
+
+impl Strategy for Medusa {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(Medusa {})
+    }
+
+    fn name(&self) -> String {
+        String::from("Medusa")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_medusa(board))
+    }
+}
@@ -0,0 +1,108 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::cell_names;
+use crate::hidden_single::HiddenSingle;
+use crate::remove_solved::RemoveSolvedFromNeighbors;
+use crate::solver::{Solution, SolveState, Solver};
+use std::fmt;
+
+// Follow up a trial placement with just the basic strategies, since
+// they are cheap and their consequences are easy to follow by hand.
+pub fn propagate(board: &Board) -> Solution {
+    Solver::with_strategies(vec![
+        RemoveSolvedFromNeighbors::new(),
+        HiddenSingle::new(),
+    ]).solve(board)
+}
+
+// A digit that can't go in a cell, because placing it there and
+// propagating the result leaves an invalid board.  substeps counts
+// the propagation steps it took to get there, including the one that
+// found the contradiction, and dead_cells lists any cells left with
+// no possible digits.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NishioPattern {
+    pub cell: usize,
+    pub digit: usize,
+    pub substeps: usize,
+    pub dead_cells: Vec<usize>,
+    pub contradiction: Option<Contradiction>,
+}
+
+impl fmt::Display for NishioPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {} makes the board invalid after {} substeps",
+               self.digit, cell_name(self.cell), self.substeps)?;
+        if let Some(ref contradiction) = self.contradiction {
+            write!(f, ": {}", contradiction)?;
+        } else if !self.dead_cells.is_empty() {
+            write!(f, ": nothing left for {}",
+                   cell_names(&self.dead_cells))?;
+        }
+        Ok(())
+    }
+}
+
+impl Pattern for NishioPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        vec![(self.cell, self.digit)]
+    }
+}
+
+// Try each digit in each unsolved cell in turn.
+pub fn find_nishio(board: &Board) -> Vec<NishioPattern> {
+    let mut result = Vec::new();
+
+    for cell in 0..81 {
+        if !matches!(board.cells[cell], Cell::Unsolved(_)) {
+            continue;
+        }
+
+        let mut digits = board.cells[cell].digits().into_iter().collect::<Vec<usize>>();
+        digits.sort_unstable();
+
+        for digit in digits {
+            let mut trial = board.clone();
+            trial.cells[cell] = Cell::Solved(digit);
+
+            let solution = propagate(&trial);
+            if solution.state != SolveState::Invalid && solution.board.valid() {
+                continue;
+            }
+
+            result.push(NishioPattern {
+                cell,
+                digit,
+                substeps: solution.steps.len() + usize::from(solution.contradiction.is_some()),
+                dead_cells: solution.board.dead_cells(),
+                contradiction: solution.contradiction,
+            });
+        }
+    }
+
+    result
+}
+
+// Nishio: assume a digit is in a cell, and if that leads to an
+// invalid board, remove it.
+pub struct Nishio;
+
+impl Strategy for Nishio {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(Nishio {})
+    }
+
+    fn name(&self) -> String {
+        String::from("Nishio")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_nishio(board))
+    }
+}
@@ -1,5 +1,6 @@
 use crate::Board;
 use crate::Cell;
+use crate::Contradiction;
 use crate::Strategy;
 
 pub struct RemoveSolvedFromNeighbors;
@@ -13,7 +14,7 @@ impl Strategy for RemoveSolvedFromNeighbors {
         String::from("RemoveSolvedFromNeighbors")
     }
 
-    fn apply(&self, board: &Board) -> Board {
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
         let mut result = board.clone();
 
         for idx in 0..81 {
@@ -21,12 +22,12 @@ impl Strategy for RemoveSolvedFromNeighbors {
                 let neighbors = Board::all_neighbors(idx);
 
                 for neighbor in neighbors {
-                    result.cells[neighbor].remove(digit).unwrap();
+                    result.remove(neighbor, digit, &self.name())?;
                 }
             }
         }
 
-        result
+        Ok((result, Vec::new()))
     }
 }
 
@@ -0,0 +1,102 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::cell_names;
+use crate::combinations;
+use std::fmt;
+
+// Two rows that each have only two places for a digit, where one
+// place in each is on the same column (the base).  Only one of the
+// base cells can hold the digit, so one of the other two places (the
+// tops) must, and the digit can be removed from every cell that sees
+// both tops.  The same works with the roles of rows and columns
+// swapped.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SkyscraperPattern {
+    pub digit: usize,
+    pub base: [usize; 2],
+    pub tops: [usize; 2],
+    pub eliminations: Vec<usize>,
+}
+
+impl fmt::Display for SkyscraperPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} has a base at {} and {}, so one of {} and {} is {}, and it is removed from {}",
+               self.digit, cell_name(self.base[0]), cell_name(self.base[1]),
+               cell_name(self.tops[0]), cell_name(self.tops[1]), self.digit,
+               cell_names(&self.eliminations))
+    }
+}
+
+impl Pattern for SkyscraperPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+pub fn find_skyscrapers(board: &Board) -> Vec<SkyscraperPattern> {
+    let mut result = Vec::new();
+
+    for digit in 1..=9 {
+        for (lines, row_based) in [(Board::rows(), true), (Board::columns(), false)] {
+            // For rows, the base cells have to share a column, and
+            // for columns they have to share a row.
+            let across = |idx: usize| if row_based { idx % 9 } else { idx / 9 };
+
+            let links = lines
+                .iter()
+                .map(|line| board.places(line, digit))
+                .filter(|places| places.len() == 2)
+                .collect::<Vec<_>>();
+
+            for pair in combinations(&links, 2) {
+                for (base0, top0) in [(pair[0][0], pair[0][1]), (pair[0][1], pair[0][0])] {
+                    for (base1, top1) in [(pair[1][0], pair[1][1]), (pair[1][1], pair[1][0])] {
+                        // Tops on the same line as each other would
+                        // make this an X-Wing instead.
+                        if across(base0) != across(base1) || across(top0) == across(top1) {
+                            continue;
+                        }
+
+                        let eliminations = (0..81)
+                            .filter(|idx| Board::sees(*idx, top0)
+                                    && Board::sees(*idx, top1)
+                                    && board.cells[*idx].contains(digit))
+                            .collect::<Vec<usize>>();
+
+                        if !eliminations.is_empty() {
+                            result.push(SkyscraperPattern {
+                                digit,
+                                base: [base0, base1],
+                                tops: [top0, top1],
+                                eliminations,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub struct Skyscraper;
+
+impl Strategy for Skyscraper {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(Skyscraper {})
+    }
+
+    fn name(&self) -> String {
+        String::from("Skyscraper")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_skyscrapers(board))
+    }
+}
@@ -0,0 +1,133 @@
+use crate::Board;
+use crate::Contradiction;
+use crate::Strategy;
+use crate::death_blossom::DeathBlossom;
+use crate::get_strategies;
+
+// How a run of the solver ended.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SolveState {
+    // Every cell holds a single digit and the board is valid.
+    Solved,
+
+    // None of the strategies could make any more progress.
+    Stuck,
+
+    // The board violates the basic sudoku constraints.
+    Invalid,
+}
+
+// A strategy that made progress, along with its own description of
+// the deductions it made.
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub strategy: String,
+    pub details: Vec<String>,
+}
+
+// The result of running the solver: the final board, how the run
+// ended, and each step that made progress, in the order they were
+// applied.  If a strategy found the board to be invalid, what it
+// found is kept in contradiction.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub board: Board,
+    pub state: SolveState,
+    pub steps: Vec<Step>,
+    pub contradiction: Option<Contradiction>,
+}
+
+pub struct Solver {
+    strategies: Vec<Box<dyn Strategy>>,
+    assume_unique: bool,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver {
+    // Make a solver that uses every registered strategy.
+    pub fn new() -> Self {
+        Self::with_strategies(get_strategies())
+    }
+
+    // Make a solver that uses only the passed-in strategies.  They
+    // should be ordered cheapest first, since the solver always
+    // starts over from the first strategy after any progress.
+    pub fn with_strategies(strategies: Vec<Box<dyn Strategy>>) -> Self {
+        Solver { strategies, assume_unique: false }
+    }
+
+    // Let the solver use strategies that rely on the puzzle having a
+    // unique solution.  On a puzzle with more than one solution they
+    // can remove digits that are part of a valid solution.
+    pub fn assume_unique(mut self, assume_unique: bool) -> Self {
+        self.assume_unique = assume_unique;
+        self
+    }
+
+    // Let the solver look for death blossoms with petals of up to
+    // max_size cells.  The search is too slow to leave on by default,
+    // so this puts the strategy in just before the cell forcing
+    // chain, the first of the trial and error strategies, or at the
+    // end if the solver doesn't use it.
+    pub fn death_blossom(mut self, max_size: usize) -> Self {
+        let idx = self.strategies
+            .iter()
+            .position(|strategy| strategy.name() == "CellForcingChain")
+            .unwrap_or(self.strategies.len());
+        self.strategies.insert(idx, DeathBlossom::with_max_size(max_size));
+        self
+    }
+
+    // Repeatedly apply the strategies to the board until it is
+    // solved, becomes invalid, or none of them change it any more.
+    pub fn solve(&self, board_in: &Board) -> Solution {
+        let mut board = board_in.clone();
+        let mut steps = Vec::new();
+
+        'progress: loop {
+            if !board.valid() {
+                return Solution { board, state: SolveState::Invalid, steps, contradiction: None };
+            }
+
+            if board.solved() {
+                return Solution { board, state: SolveState::Solved, steps, contradiction: None };
+            }
+
+            for strategy in &self.strategies {
+                if strategy.requires_uniqueness() && !self.assume_unique {
+                    continue;
+                }
+
+                let (updated_board, details) = match strategy.deduce(&board) {
+                    Ok(deduced) => deduced,
+                    Err(contradiction) => {
+                        return Solution {
+                            board,
+                            state: SolveState::Invalid,
+                            steps,
+                            contradiction: Some(contradiction),
+                        };
+                    },
+                };
+
+                if updated_board != board {
+                    // Progress was made, so go back to the cheapest
+                    // strategy and try again from there.
+                    steps.push(Step {
+                        strategy: strategy.name(),
+                        details,
+                    });
+                    board = updated_board;
+                    continue 'progress;
+                }
+            }
+
+            return Solution { board, state: SolveState::Stuck, steps, contradiction: None };
+        }
+    }
+}
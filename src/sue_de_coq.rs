@@ -0,0 +1,167 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::candidate_names;
+use crate::cell_names;
+use crate::combinations;
+use crate::digit_names;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// Two or three cells where a box crosses a row or column, which
+// between them could be at least two more digits than there are
+// cells, together with some cells from the rest of the line and some
+// from the rest of the box.  If the line cells and box cells have no
+// digits in common, and all of the cells together can only be as many
+// digits as there are cells, then each digit is in the set exactly
+// once.  Digits the line cells could be are then on the line, digits
+// the box cells could be are in the box, and the remaining digits of
+// the crossing cells are in both, so they can be removed from the rest
+// of the line and box accordingly.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SueDeCoqPattern {
+    pub cells: Vec<usize>,
+    pub digits: Vec<usize>,
+    pub line_cells: Vec<usize>,
+    pub line_digits: Vec<usize>,
+    pub box_cells: Vec<usize>,
+    pub box_digits: Vec<usize>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl fmt::Display for SueDeCoqPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}) splits into {} ({}) on the line and {} ({}) in the box, so {} can be removed",
+               cell_names(&self.cells), digit_names(&self.digits),
+               cell_names(&self.line_cells), digit_names(&self.line_digits),
+               cell_names(&self.box_cells), digit_names(&self.box_digits),
+               candidate_names(&self.eliminations))
+    }
+}
+
+impl Pattern for SueDeCoqPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+}
+
+// Every non-empty set of the unsolved cells, along with the digits
+// they could be between them.
+fn subsets(board: &Board, cells: &[usize]) -> Vec<(Vec<usize>, BTreeSet<usize>)> {
+    let unsolved = cells
+        .iter()
+        .filter(|idx| matches!(board.cells[**idx], Cell::Unsolved(_)))
+        .copied()
+        .collect::<Vec<usize>>();
+
+    (1..=unsolved.len())
+        .flat_map(|size| combinations(&unsolved, size))
+        .map(|subset| {
+            let digits = subset.iter().flat_map(|idx| board.cells[*idx].digits()).collect();
+            (subset, digits)
+        })
+        .collect()
+}
+
+// The digits that can be removed from the cells of the group that
+// aren't in keep.
+fn outside(board: &Board, group: &[usize], keep: &[usize], digits: &BTreeSet<usize>) -> Vec<Candidate> {
+    group
+        .iter()
+        .filter(|idx| !keep.contains(idx) && matches!(board.cells[**idx], Cell::Unsolved(_)))
+        .flat_map(|idx| digits
+                  .iter()
+                  .filter(|digit| board.cells[*idx].contains(**digit))
+                  .map(|digit| (*idx, *digit)))
+        .collect()
+}
+
+pub fn find_sue_de_coqs(board: &Board) -> Vec<SueDeCoqPattern> {
+    let mut result = Vec::new();
+
+    let mut lines = Board::rows();
+    lines.extend(Board::columns());
+
+    for box_ in Board::boxes() {
+        for line in &lines {
+            let shared = box_
+                .iter()
+                .filter(|idx| line.contains(idx))
+                .copied()
+                .collect::<Vec<usize>>();
+
+            if shared.is_empty() {
+                continue;
+            }
+
+            let rest_of_line = line.iter().filter(|idx| !shared.contains(idx)).copied().collect::<Vec<usize>>();
+            let rest_of_box = box_.iter().filter(|idx| !shared.contains(idx)).copied().collect::<Vec<usize>>();
+            let line_sets = subsets(board, &rest_of_line);
+            let box_sets = subsets(board, &rest_of_box);
+
+            for (cells, digits) in subsets(board, &shared) {
+                if cells.len() < 2 || digits.len() < cells.len() + 2 {
+                    continue;
+                }
+
+                for (line_cells, line_digits) in &line_sets {
+                    for (box_cells, box_digits) in &box_sets {
+                        if !line_digits.is_disjoint(box_digits) {
+                            continue;
+                        }
+
+                        let count = digits.iter().chain(line_digits).chain(box_digits).collect::<BTreeSet<_>>().len();
+                        if count != cells.len() + line_cells.len() + box_cells.len() {
+                            continue;
+                        }
+
+                        let on_line = line_digits.union(&digits.difference(box_digits).copied().collect()).copied().collect();
+                        let in_box = box_digits.union(&digits.difference(line_digits).copied().collect()).copied().collect();
+
+                        let keep_line = cells.iter().chain(line_cells).copied().collect::<Vec<usize>>();
+                        let keep_box = cells.iter().chain(box_cells).copied().collect::<Vec<usize>>();
+
+                        let mut eliminations = outside(board, line, &keep_line, &on_line);
+                        eliminations.extend(outside(board, &box_, &keep_box, &in_box));
+                        eliminations.sort_unstable();
+                        eliminations.dedup();
+
+                        if !eliminations.is_empty() {
+                            result.push(SueDeCoqPattern {
+                                cells: cells.clone(),
+                                digits: digits.iter().copied().collect(),
+                                line_cells: line_cells.clone(),
+                                line_digits: line_digits.iter().copied().collect(),
+                                box_cells: box_cells.clone(),
+                                box_digits: box_digits.iter().copied().collect(),
+                                eliminations,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub struct SueDeCoq;
+
+impl Strategy for SueDeCoq {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(SueDeCoq {})
+    }
+
+    fn name(&self) -> String {
+        String::from("SueDeCoq")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_sue_de_coqs(board))
+    }
+}
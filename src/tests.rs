@@ -1,7 +1,31 @@
 use super::*;
 
 use remove_solved::RemoveSolvedFromNeighbors;
-use disjoint_subset::NakedPair;
+use aic::{Aic, AicType, find_aics};
+use als::{AlmostLockedSet, AlsXz, AlsXyWing, almost_locked_sets, find_als_xz};
+use bug::{Bug, find_bug};
+use coloring::{SimpleColoring, MultiColoring, ColoringRule, clusters, find_simple_coloring, find_multi_coloring};
+use death_blossom::{DeathBlossom, find_death_blossoms};
+use disjoint_subset::{NakedPair, NakedTriple, NakedQuad};
+use empty_rectangle::{EmptyRectangle, EmptyRectanglePattern, find_empty_rectangles};
+use fish::{XWing, Swordfish, Jellyfish, FishPattern, find_fish};
+use fish::{FinnedXWing, FinnedSwordfish, SashimiXWing, SashimiSwordfish, FinnedFishPattern, find_finned_fish};
+use forcing_chain::{CellForcingChain, UnitForcingChain, find_cell_forcing_chains};
+use guess::{Backtracker, Guess, guess_lines};
+use hidden_single::HiddenSingle;
+use hidden_subset::{HiddenPair, HiddenTriple, HiddenQuad};
+use locked_candidates::LockedCandidates;
+use medusa::{Medusa, medusa_clusters, find_medusa};
+use nishio::{Nishio, find_nishio};
+use skyscraper::{Skyscraper, SkyscraperPattern, find_skyscrapers};
+use solver::{Solver, SolveState};
+use sue_de_coq::{SueDeCoq, find_sue_de_coqs};
+use two_string_kite::{TwoStringKite, KitePattern, find_kites};
+use unique_rectangle::{UniqueRectangle, HiddenUniqueRectangle, UniqueRectangleType, find_unique_rectangles};
+use w_wing::{WWing, WWingPattern, find_w_wings};
+use x_cycle::{XCycle, XCycleKind, find_x_cycles};
+use xy_chain::{XYChain, find_xy_chains};
+use xy_wing::{XYWing, XYZWing, WingPattern, find_wings};
 use std::collections::HashSet;
 
 #[test]
@@ -49,6 +73,26 @@ fn test_cell_remove_from_solved() -> Result<(), String> {
     Ok(())
 }
 
+#[test]
+fn test_cell_remove_to_dead() {
+    let mut c = Cell::from_digits([3,8]);
+    c.remove(3).unwrap();
+    assert_eq!(c, Cell::Solved(8));
+    assert!(c.remove(8).is_err());
+    assert_eq!(c, Cell::Dead);
+    assert_eq!(c.count(), 0);
+    assert!(c.digits().is_empty());
+
+    // Nothing more can be removed from a dead cell
+    c.remove(8).unwrap();
+    assert_eq!(c, Cell::Dead);
+}
+
+#[test]
+fn test_cell_from_no_digits() {
+    assert_eq!(Cell::from_digits([]), Cell::Dead);
+}
+
 #[test]
 fn test_cell_count() {
     assert_eq!(Cell::new().count(), 9);
@@ -65,6 +109,16 @@ fn test_cell_digits() {
     assert_eq!(Cell::Solved(7).digits(), HashSet::from([7]));
 }
 
+#[test]
+fn test_cell_contains() {
+    assert!(Cell::new().contains(5));
+    assert!(Cell::from_digits([1,2,3]).contains(2));
+    assert!(!Cell::from_digits([1,2,3]).contains(4));
+    assert!(Cell::Solved(7).contains(7));
+    assert!(!Cell::Solved(7).contains(6));
+    assert!(!Cell::Dead.contains(7));
+}
+
 #[test]
 fn test_empty_board() {
     let b = Board::new();
@@ -156,6 +210,25 @@ fn test_get_cells() {
     }
 }
 
+#[test]
+fn test_sees() {
+    assert!(Board::sees(0, 8));
+    assert!(Board::sees(0, 72));
+    assert!(Board::sees(0, 20));
+    assert!(!Board::sees(0, 0));
+    assert!(!Board::sees(0, 21));
+    assert!(!Board::sees(40, 80));
+}
+
+#[test]
+fn test_box_of() {
+    for (box_idx, box_) in Board::boxes().iter().enumerate() {
+        for idx in box_ {
+            assert_eq!(Board::box_of(*idx), box_idx);
+        }
+    }
+}
+
 #[test]
 fn test_row_neighbors() {
     let rn = Board::row_neighbors(14);
@@ -231,6 +304,33 @@ fn test_loaded_board_invalid() {
     assert!(!b.valid());
 }
 
+#[test]
+fn test_dead_cells() {
+    let mut b = Board::from_str("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8...9..36..").unwrap();
+    assert!(b.dead_cells().is_empty());
+
+    b.cells[10] = Cell::Dead;
+    b.cells[44] = Cell::from_digits([]);
+    assert_eq!(b.dead_cells(), [10, 44]);
+    assert!(!b.valid());
+    assert!(!b.solved());
+}
+
+#[test]
+fn test_board_remove_reports_dead_cell() {
+    let mut b = Board::new();
+    b.cells[30] = Cell::from_digits([2,7]);
+
+    b.remove(30, 2, "Test").unwrap();
+    let contradiction = b.remove(30, 7, "Test").unwrap_err();
+
+    assert_eq!(contradiction.strategy, "Test");
+    assert_eq!(contradiction.cell, Some(30));
+    assert_eq!(contradiction.digit, Some(7));
+    assert_eq!(b.dead_cells(), [30]);
+    assert!(!b.valid());
+}
+
 #[test]
 fn test_empty_board_unsolved() {
     assert!(!Board::new().solved());
@@ -249,7 +349,7 @@ fn test_strategies_produce_valid_boards() {
     assert!(b.valid());
 
     for strategy in get_strategies() {
-        let updated_board = strategy.apply(&b);
+        let updated_board = strategy.apply(&b).unwrap();
         assert!(updated_board.valid(), "while applying strategy {}", strategy.name());
     }
 }
@@ -258,7 +358,7 @@ fn test_strategies_produce_valid_boards() {
 fn test_naked_pair() {
     let board_in = Board::from_str("4..27.6..798156234.2.84...7237468951849531726561792843.82.15479.7..243....4.87..2").unwrap();
 
-    let board = RemoveSolvedFromNeighbors::new().apply(&board_in);
+    let board = RemoveSolvedFromNeighbors::new().apply(&board_in).unwrap();
 
     // Check that the board has a naked pair as expected on the last
     // row, and values on that row that can be eliminated due to it.
@@ -268,7 +368,7 @@ fn test_naked_pair() {
     assert_eq!(board.cells[72], Cell::from_digits([1,3,6,9]));
     assert_eq!(board.cells[79], Cell::from_digits([1,6]));
 
-    let updated_board = NakedPair::new().apply(&board);
+    let updated_board = NakedPair::new().apply(&board).unwrap();
 
     assert_ne!(updated_board, board);
 
@@ -290,3 +390,1646 @@ fn test_naked_pair() {
         println!("{}", str);
     }
 }
+
+// Run the passed-in strategies over the puzzle until they get
+// stuck, and return the resulting board.
+fn stuck_board(puzzle: &str, strategies: Vec<Box<dyn Strategy>>) -> Board {
+    let solution = Solver::with_strategies(strategies).solve(&Board::from_str(puzzle).unwrap());
+    assert_eq!(solution.state, SolveState::Stuck);
+    solution.board
+}
+
+// All of the registered strategies that come before the named one.
+fn strategies_before(name: &str) -> Vec<Box<dyn Strategy>> {
+    get_strategies()
+        .into_iter()
+        .take_while(|strategy| strategy.name() != name)
+        .collect()
+}
+
+// Check that every cell on the board can still hold the digit it has
+// in the known solution, ie that nothing was wrongly eliminated.
+fn assert_consistent(board: &Board, solution: &str) {
+    let solution = Board::from_str(solution).unwrap();
+    for idx in 0..81 {
+        let Cell::Solved(digit) = solution.cells[idx] else { panic!("solution is not solved") };
+        assert!(board.cells[idx].contains(digit), "cell {} can no longer hold {}", idx, digit);
+    }
+}
+
+// Get the puzzle stuck with every strategy that comes before the
+// passed-in one, then check that the strategy makes some progress,
+// says how, and doesn't eliminate anything from the known solution.
+fn assert_progress(strategy: Box<dyn Strategy>, puzzle: &str, solution: &str) {
+    let board = stuck_board(puzzle, strategies_before(&strategy.name()));
+
+    let (updated_board, details) = strategy.deduce(&board).unwrap();
+
+    assert_ne!(updated_board, board, "{} made no progress", strategy.name());
+    assert!(!details.is_empty());
+    assert_consistent(&updated_board, solution);
+}
+
+#[test]
+fn test_combinations() {
+    assert_eq!(combinations(&[1,2,3,4], 2),
+               [[1,2],[1,3],[1,4],[2,3],[2,4],[3,4]]);
+    assert_eq!(combinations(&[1,2,3], 3), [[1,2,3]]);
+    assert!(combinations(&[1,2], 3).is_empty());
+}
+
+#[test]
+fn test_naked_triple() {
+    // No two cells share the same pair of digits, but between them
+    // cells 0, 4 and 8 can only hold 1, 2 and 3.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[4] = Cell::from_digits([2,3]);
+    board.cells[8] = Cell::from_digits([1,3]);
+
+    // A naked pair can't find anything here
+    assert_eq!(NakedPair::new().apply(&board).unwrap(), board);
+
+    let updated_board = NakedTriple::new().apply(&board).unwrap();
+
+    for idx in [1,2,3,5,6,7] {
+        assert_eq!(updated_board.cells[idx], Cell::from_digits([4,5,6,7,8,9]));
+    }
+    assert_eq!(updated_board.cells[0], Cell::from_digits([1,2]));
+    assert_eq!(updated_board.cells[4], Cell::from_digits([2,3]));
+    assert_eq!(updated_board.cells[8], Cell::from_digits([1,3]));
+}
+
+#[test]
+fn test_naked_triple_on_loaded_board() {
+    let board = stuck_board(".....3..6.74.......3.6..9.....9.4.2......81..7.5...3...4..1.5...6...9....17.5.6..",
+                            vec![RemoveSolvedFromNeighbors::new(), HiddenSingle::new(), NakedPair::new()]);
+
+    let updated_board = NakedTriple::new().apply(&board).unwrap();
+
+    assert_ne!(updated_board, board);
+    assert_consistent(&updated_board, "259143876674895231831627954186934725423578169795261348348716592562389417917452683");
+}
+
+#[test]
+fn test_naked_quad() {
+    // Cells 0, 9, 27 and 72 in the first column can only hold 1
+    // through 4 between them.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[9] = Cell::from_digits([2,3]);
+    board.cells[27] = Cell::from_digits([3,4]);
+    board.cells[72] = Cell::from_digits([1,4]);
+
+    assert_eq!(NakedTriple::new().apply(&board).unwrap(), board);
+
+    let updated_board = NakedQuad::new().apply(&board).unwrap();
+
+    for idx in [18,36,45,54,63] {
+        assert_eq!(updated_board.cells[idx], Cell::from_digits([5,6,7,8,9]));
+    }
+    assert_eq!(NakedQuad::new().name(), "NakedQuad");
+}
+
+#[test]
+fn test_naked_triple_reports_contradiction() {
+    // Three cells can't share just two digits
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[1] = Cell::from_digits([1,2]);
+    board.cells[2] = Cell::from_digits([1,2]);
+
+    let contradiction = NakedTriple::new().apply(&board).unwrap_err();
+
+    assert_eq!(contradiction.strategy, "NakedTriple");
+    assert_eq!(contradiction.group, Some(Board::rows()[0].clone()));
+}
+
+#[test]
+fn test_hidden_pair() {
+    // 1 and 2 can only go in cells 3 and 7 of the first row, so those
+    // cells can't be anything else.
+    let mut board = Board::new();
+    for idx in [0,1,2,4,5,6,8] {
+        board.cells[idx] = Cell::from_digits([3,4,5,6,7,8,9]);
+    }
+
+    assert_eq!(NakedPair::new().apply(&board).unwrap(), board);
+
+    let updated_board = HiddenPair::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[3], Cell::from_digits([1,2]));
+    assert_eq!(updated_board.cells[7], Cell::from_digits([1,2]));
+    for idx in [0,1,2,4,5,6,8] {
+        assert_eq!(updated_board.cells[idx], board.cells[idx]);
+    }
+}
+
+#[test]
+fn test_hidden_triple_on_loaded_board() {
+    let board = stuck_board("..943...55.4...8..6...8....3..1.....8...2...92..8.3..1....7..6...7..2...9...4..52",
+                            strategies_before("HiddenTriple"));
+
+    let updated_board = HiddenTriple::new().apply(&board).unwrap();
+
+    assert_ne!(updated_board, board);
+    assert_consistent(&updated_board, "789436125534219876612785493396154287841627539275893641123578964457962318968341752");
+}
+
+#[test]
+fn test_hidden_quad_on_loaded_board() {
+    let board = stuck_board("6.......8.735.8.........237..97..36......35...62....4...8..6.7.79....4.5..4......",
+                            strategies_before("HiddenQuad"));
+
+    let updated_board = HiddenQuad::new().apply(&board).unwrap();
+
+    assert_ne!(updated_board, board);
+    assert_consistent(&updated_board, "621374958973528614845619237589742361417963582362185749258496173796831425134257896");
+}
+
+#[test]
+fn test_hidden_triple_reports_contradiction() {
+    // 1, 2 and 3 can only go in two cells of the first box
+    let mut board = Board::new();
+    for idx in [0,1,2,9,10,11,18] {
+        board.cells[idx] = Cell::from_digits([4,5,6,7,8,9]);
+    }
+
+    let contradiction = HiddenTriple::new().apply(&board).unwrap_err();
+
+    assert_eq!(contradiction.strategy, "HiddenTriple");
+    assert_eq!(contradiction.group, Some(Board::boxes()[0].clone()));
+}
+
+#[test]
+fn test_locked_candidates_pointing() {
+    // The only places for a 4 in the first box are on the top row, so
+    // there can't be a 4 anywhere else on the top row.
+    let mut board = Board::new();
+    for idx in [9,10,11,18,19,20] {
+        board.cells[idx].remove(4).unwrap();
+    }
+
+    let updated_board = LockedCandidates::new().apply(&board).unwrap();
+
+    for idx in 3..9 {
+        assert!(!updated_board.cells[idx].contains(4));
+    }
+    for idx in [0,1,2] {
+        assert!(updated_board.cells[idx].contains(4));
+    }
+    assert!(updated_board.cells[27].contains(4));
+}
+
+#[test]
+fn test_locked_candidates_box_line_reduction() {
+    // The only places for a 6 in the last column are in the bottom
+    // right box, so there can't be a 6 anywhere else in that box.
+    let mut board = Board::new();
+    for idx in [8,17,26,35,44,53] {
+        board.cells[idx].remove(6).unwrap();
+    }
+
+    let updated_board = LockedCandidates::new().apply(&board).unwrap();
+
+    for idx in [60,61,69,70,78,79] {
+        assert!(!updated_board.cells[idx].contains(6));
+    }
+    for idx in [62,71,80,59] {
+        assert!(updated_board.cells[idx].contains(6));
+    }
+}
+
+#[test]
+fn test_locked_candidates_on_loaded_board() {
+    let board = stuck_board("...92.....5...31.2.......34..5....7...2.9...5.3.7....9.1.47..2.3....97...29..8..1",
+                            strategies_before("LockedCandidates"));
+
+    let updated_board = LockedCandidates::new().apply(&board).unwrap();
+
+    assert_ne!(updated_board, board);
+    assert_consistent(&updated_board, "843921567657843192291567834985132476172694385436785219518476923364219758729358641");
+}
+
+#[test]
+fn test_x_wing() {
+    // A 7 in rows 1 and 5 can only go in columns 2 and 6.
+    let mut board = Board::new();
+    for idx in Board::rows()[1].iter().chain(Board::rows()[5].iter()) {
+        if idx % 9 != 2 && idx % 9 != 6 {
+            board.cells[*idx].remove(7).unwrap();
+        }
+    }
+
+    assert_eq!(find_fish(&board, 2),
+               [FishPattern {
+                   digit: 7,
+                   row_based: true,
+                   base: vec![1,5],
+                   cover: vec![2,6],
+                   eliminations: vec![2,20,29,38,56,65,74,6,24,33,42,60,69,78],
+               }]);
+
+    let updated_board = XWing::new().apply(&board).unwrap();
+
+    for idx in [2,20,29,38,56,65,74,6,24,33,42,60,69,78] {
+        assert!(!updated_board.cells[idx].contains(7));
+    }
+    for idx in [11,15,47,51,0,80] {
+        assert!(updated_board.cells[idx].contains(7));
+    }
+
+    assert_eq!(XWing::new().explain(&board).len(), 1);
+}
+
+#[test]
+fn test_swordfish_on_loaded_board() {
+    let board = stuck_board(".5..7.49..76.5....2..1.......831...4......1.8....257...4..6..71.........5..9..6..",
+                            strategies_before("Swordfish"));
+
+    assert!(find_fish(&board, 2).is_empty());
+    assert!(!find_fish(&board, 3).is_empty());
+
+    let updated_board = Swordfish::new().apply(&board).unwrap();
+
+    assert_ne!(updated_board, board);
+    assert_consistent(&updated_board, "153278496876459312294136587968317254725694138431825769349562871612783945587941623");
+}
+
+#[test]
+fn test_jellyfish() {
+    // A 3 in rows 0, 2, 4 and 8 can only go in columns 1, 3, 5 and
+    // 7, with no smaller fish among them.
+    let mut board = Board::new();
+    for (row, cols) in [(0, [1,3]), (2, [3,5]), (4, [5,7]), (8, [7,1])] {
+        for col in (0..9).filter(|col| !cols.contains(col)) {
+            board.cells[row*9 + col].remove(3).unwrap();
+        }
+    }
+
+    assert!(find_fish(&board, 2).is_empty());
+    assert!(find_fish(&board, 3).is_empty());
+
+    let fish = find_fish(&board, 4);
+    assert_eq!(fish.len(), 1);
+    assert_eq!(fish[0].base, [0,2,4,8]);
+    assert_eq!(fish[0].cover, [1,3,5,7]);
+
+    let updated_board = Jellyfish::new().apply(&board).unwrap();
+
+    for row in [1,3,5,6,7] {
+        for col in [1,3,5,7] {
+            assert!(!updated_board.cells[row*9 + col].contains(3));
+        }
+    }
+    assert!(updated_board.cells[9].contains(3));
+}
+
+// Make a board where the digit can only go in the listed columns of
+// each listed row.
+fn board_with_places(digit: usize, places: &[(usize, &[usize])]) -> Board {
+    let mut board = Board::new();
+    for (row, cols) in places {
+        for col in (0..9).filter(|col| !cols.contains(col)) {
+            board.cells[row*9 + col].remove(digit).unwrap();
+        }
+    }
+    board
+}
+
+#[test]
+fn test_finned_x_wing() {
+    // A 5 in rows 1 and 4 can only go in columns 1 and 7, except for
+    // a fin at row4 col8.
+    let board = board_with_places(5, &[(1, &[1,7]), (4, &[1,7,8])]);
+
+    assert!(find_fish(&board, 2).is_empty());
+    assert_eq!(find_finned_fish(&board, 2),
+               [FinnedFishPattern {
+                   fish: FishPattern {
+                       digit: 5,
+                       row_based: true,
+                       base: vec![1,4],
+                       cover: vec![1,7],
+                       eliminations: vec![34,52],
+                   },
+                   fins: vec![44],
+                   sashimi: false,
+               }]);
+
+    // Either the fin or the X-Wing holds the 5, and both rule out
+    // the cells of column 7 in the fin's box.
+    let updated_board = FinnedXWing::new().apply(&board).unwrap();
+    assert!(!updated_board.cells[34].contains(5));
+    assert!(!updated_board.cells[52].contains(5));
+    assert!(updated_board.cells[70].contains(5));
+    assert!(updated_board.cells[46].contains(5));
+
+    assert_eq!(SashimiXWing::new().apply(&board).unwrap(), board);
+}
+
+#[test]
+fn test_sashimi_x_wing() {
+    // Without its fins at row4 col6 and row4 col8, row 4 would only
+    // have one place for a 5 on the cover columns.
+    let board = board_with_places(5, &[(1, &[1,7]), (4, &[1,6,8])]);
+
+    let finned = find_finned_fish(&board, 2);
+    assert_eq!(finned.len(), 1);
+    assert!(finned[0].sashimi);
+    assert_eq!(finned[0].fins, [42,44]);
+    assert_eq!(finned[0].fish.eliminations, [34,52]);
+
+    let updated_board = SashimiXWing::new().apply(&board).unwrap();
+    assert!(!updated_board.cells[34].contains(5));
+    assert!(!updated_board.cells[52].contains(5));
+
+    assert_eq!(FinnedXWing::new().apply(&board).unwrap(), board);
+}
+
+#[test]
+fn test_finned_fish_on_loaded_boards() {
+    for (strategy, puzzle, solution) in [
+        (FinnedXWing::new(),
+         ".3..6..951.98.2.3................3.2..2..4.....89.76...9...........13.7...7..9...",
+         "834761295159842736726395814971658342562134987348927651493576128685213479217489563"),
+        (SashimiXWing::new(),
+         ".3..6..951.98.2.3................3.2..2..4.....89.76...9...........13.7...7..9...",
+         "834761295159842736726395814971658342562134987348927651493576128685213479217489563"),
+        (FinnedSwordfish::new(),
+         "..7.1..8..5......7.......5..2.5....434.9..81.....2......14.....832.6..4......81.9",
+         "297615483156843927483792651629581374345976812718324596961457238832169745574238169"),
+        (SashimiSwordfish::new(),
+         "7....6.9....35.1........74...75.3.....1...2.7....2...94.8..25.66.2........54.....",
+         "713246895849357162526918743267593481951684237384721659498132576632875914175469328"),
+    ] {
+        assert_progress(strategy, puzzle, solution);
+    }
+}
+
+#[test]
+fn test_skyscraper() {
+    // A 1 in row 0 is at col 0 or col 4, and in row 4 at col 0 or
+    // col 5.  Both can't be in col 0, so one of row0 col4 and row4
+    // col5 is a 1.
+    let board = board_with_places(1, &[(0, &[0,4]), (4, &[0,5])]);
+
+    assert!(find_fish(&board, 2).is_empty());
+    assert_eq!(find_skyscrapers(&board),
+               [SkyscraperPattern {
+                   digit: 1,
+                   base: [0,36],
+                   tops: [4,41],
+                   eliminations: vec![14,23,31,49],
+               }]);
+
+    let updated_board = Skyscraper::new().apply(&board).unwrap();
+
+    for idx in [14,23,31,49] {
+        assert!(!updated_board.cells[idx].contains(1));
+    }
+    assert!(updated_board.cells[13].contains(1));
+}
+
+#[test]
+fn test_explain_lists_patterns_that_changed_the_board() {
+    let board = board_with_places(1, &[(0, &[0,4]), (4, &[0,5])]);
+    let pattern = find_skyscrapers(&board).remove(0);
+
+    // The second copy has nothing left to remove, so it isn't listed.
+    let (updated_board, details) = apply_patterns("Skyscraper", &board, [pattern.clone(), pattern.clone()]).unwrap();
+
+    assert_eq!(updated_board, Skyscraper::new().apply(&board).unwrap());
+    assert_eq!(details, [pattern.to_string()]);
+    assert_eq!(Skyscraper::new().explain(&board), details);
+}
+
+#[test]
+fn test_two_string_kite() {
+    // A 2 in row 1 is at col 1 or col 7, and in col 2 at row 0 or
+    // row 6.  row1 col1 and row0 col2 share a box so can't both be 2,
+    // leaving one of row1 col7 and row6 col2 as a 2.
+    let mut board = board_with_places(2, &[(1, &[1,7])]);
+    for idx in Board::columns()[2].iter().filter(|idx| **idx != 2 && **idx != 56) {
+        board.cells[*idx].remove(2).unwrap();
+    }
+
+    assert_eq!(find_kites(&board),
+               [KitePattern {
+                   digit: 2,
+                   row: [10,16],
+                   column: [2,56],
+                   eliminations: vec![61],
+               }]);
+
+    let updated_board = TwoStringKite::new().apply(&board).unwrap();
+
+    assert!(!updated_board.cells[61].contains(2));
+    assert!(updated_board.cells[60].contains(2));
+}
+
+#[test]
+fn test_empty_rectangle() {
+    // A 3 in the middle box is confined to row 4 and column 4, and in
+    // column 7 it is at row 4 or row 7.  If row7 col4 were a 3, row7
+    // col7 couldn't be, so row4 col7 would be, leaving nowhere in the
+    // middle box for a 3.
+    let mut board = Board::new();
+    for idx in [30,32,48,50] {
+        board.cells[idx].remove(3).unwrap();
+    }
+    for idx in Board::columns()[7].iter().filter(|idx| **idx != 43 && **idx != 70) {
+        board.cells[*idx].remove(3).unwrap();
+    }
+
+    assert_eq!(find_empty_rectangles(&board),
+               [EmptyRectanglePattern {
+                   digit: 3,
+                   box_idx: 4,
+                   row: 4,
+                   column: 4,
+                   link: [43,70],
+                   elimination: 67,
+               }]);
+
+    let updated_board = EmptyRectangle::new().apply(&board).unwrap();
+
+    assert!(!updated_board.cells[67].contains(3));
+    assert!(updated_board.cells[66].contains(3));
+}
+
+#[test]
+fn test_single_digit_patterns_on_loaded_board() {
+    // These all find something once the basic strategies are stuck,
+    // before any fish are tried.
+    let board = stuck_board(".3..6..951.98.2.3................3.2..2..4.....89.76...9...........13.7...7..9...",
+                            strategies_before("XWing"));
+
+    for strategy in [Skyscraper::new(), TwoStringKite::new(), EmptyRectangle::new()] {
+        let updated_board = strategy.apply(&board).unwrap();
+
+        assert_ne!(updated_board, board, "{} made no progress", strategy.name());
+        assert!(!strategy.explain(&board).is_empty());
+        assert_consistent(&updated_board, "834761295159842736726395814971658342562134987348927651493576128685213479217489563");
+    }
+}
+
+#[test]
+fn test_xy_wing() {
+    // Whether the pivot at row0 col0 is a 1 or a 2, one of the
+    // pincers at row0 col4 and row2 col0 has to be a 3.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[4] = Cell::from_digits([1,3]);
+    board.cells[18] = Cell::from_digits([2,3]);
+
+    assert_eq!(find_wings(&board, 2),
+               [WingPattern {
+                   digit: 3,
+                   pivot: 0,
+                   pincers: [4,18],
+                   eliminations: vec![1,2,21,22,23],
+               }]);
+
+    let updated_board = XYWing::new().apply(&board).unwrap();
+
+    for idx in [1,2,21,22,23] {
+        assert!(!updated_board.cells[idx].contains(3));
+    }
+    assert!(updated_board.cells[3].contains(3));
+    assert_eq!(XYWing::new().explain(&board),
+               ["pivot row0 col0 with pincers row0 col4 and row2 col0 removes 3 from row0 col1, row0 col2, row2 col3, row2 col4, row2 col5"]);
+}
+
+#[test]
+fn test_xyz_wing() {
+    // The pivot can also be the 3 itself, so only cells that see all
+    // three cells lose the 3.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2,3]);
+    board.cells[4] = Cell::from_digits([1,3]);
+    board.cells[18] = Cell::from_digits([2,3]);
+
+    assert!(find_wings(&board, 2).is_empty());
+    assert_eq!(find_wings(&board, 3),
+               [WingPattern {
+                   digit: 3,
+                   pivot: 0,
+                   pincers: [4,18],
+                   eliminations: vec![1,2],
+               }]);
+
+    let updated_board = XYZWing::new().apply(&board).unwrap();
+
+    assert!(!updated_board.cells[1].contains(3));
+    assert!(!updated_board.cells[2].contains(3));
+    assert!(updated_board.cells[21].contains(3));
+}
+
+#[test]
+fn test_wings_on_loaded_boards() {
+    for (strategy, puzzle, solution) in [
+        (XYWing::new(),
+         "2.....7.....2...6.4..1.9...95...83.......651.6.8....4....7.1......82.....9..3.4..",
+         "261583794589247163473169852957418326324976518618352947846791235135824679792635481"),
+        (XYZWing::new(),
+         "....2.391..431.5......6.......75.....29......1.6....3..5.43..6......9....4.....8.",
+         "765824391894317526312965847483756219529143678176298435958431762237689154641572983"),
+    ] {
+        assert_progress(strategy, puzzle, solution);
+    }
+}
+
+#[test]
+fn test_w_wing() {
+    // row0 col0 and row4 col4 can both only be 4 or 7, and a 4 in
+    // column 2 is either at row0 col2 or row4 col2.  If neither cell
+    // were a 7 they would both be 4, leaving column 2 without a 4.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([4,7]);
+    board.cells[40] = Cell::from_digits([4,7]);
+    for idx in Board::columns()[2].iter().filter(|idx| **idx != 2 && **idx != 38) {
+        board.cells[*idx].remove(4).unwrap();
+    }
+
+    assert_eq!(find_w_wings(&board),
+               [WWingPattern {
+                   digit: 7,
+                   link_digit: 4,
+                   cells: [0,40],
+                   link: [2,38],
+                   eliminations: vec![4,36],
+               }]);
+
+    let updated_board = WWing::new().apply(&board).unwrap();
+
+    assert!(!updated_board.cells[4].contains(7));
+    assert!(!updated_board.cells[36].contains(7));
+    assert!(updated_board.cells[4].contains(4));
+    assert!(updated_board.cells[8].contains(7));
+}
+
+#[test]
+fn test_w_wing_on_loaded_board() {
+    let board = stuck_board("..943...55.4...8..6...8....3..1.....8...2...92..8.3..1....7..6...7..2...9...4..52",
+                            strategies_before("WWing"));
+
+    let updated_board = WWing::new().apply(&board).unwrap();
+
+    assert_ne!(updated_board, board);
+    assert_consistent(&updated_board, "789436125534219876612785493396154287841627539275893641123578964457962318968341752");
+}
+
+// Make a board where the digit can only go in the listed cells of
+// each listed group.
+fn board_with_links(digit: usize, links: &[(&[usize], &[usize])]) -> Board {
+    let mut board = Board::new();
+    for (group, places) in links {
+        for idx in group.iter().filter(|idx| !places.contains(idx)) {
+            board.cells[*idx].remove(digit).unwrap();
+        }
+    }
+    board
+}
+
+#[test]
+fn test_coloring_clusters() {
+    let board = board_with_links(5, &[(&Board::rows()[0], &[0,4]),
+                                      (&Board::columns()[4], &[4,40]),
+                                      (&Board::rows()[4], &[40,36])]);
+
+    assert_eq!(clusters(&board, 5), [[vec![0,40], vec![4,36]]]);
+}
+
+#[test]
+fn test_simple_coloring_trap() {
+    // The chain row0 col0 - row0 col4 - row4 col4 - row4 col0 puts
+    // opposite colors on row0 col0 and row4 col0, so one of them is
+    // the 5 for column 0.
+    let board = board_with_links(5, &[(&Board::rows()[0], &[0,4]),
+                                      (&Board::columns()[4], &[4,40]),
+                                      (&Board::rows()[4], &[40,36])]);
+
+    let patterns = find_simple_coloring(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].rule, ColoringRule::Trap);
+    assert_eq!(patterns[0].eliminations, [9,18,27,45,54,63,72]);
+
+    let updated_board = SimpleColoring::new().apply(&board).unwrap();
+
+    for idx in [9,18,27,45,54,63,72] {
+        assert!(!updated_board.cells[idx].contains(5));
+    }
+    for idx in [0,4,36,40] {
+        assert!(updated_board.cells[idx].contains(5));
+    }
+}
+
+#[test]
+fn test_simple_coloring_wrap() {
+    // Extending the chain to row2 col2 gives it the same color as
+    // row0 col0 in the same box, so that color can't be a 5.
+    let board = board_with_links(5, &[(&Board::rows()[0], &[0,4]),
+                                      (&Board::columns()[4], &[4,40]),
+                                      (&Board::rows()[4], &[40,38]),
+                                      (&Board::columns()[2], &[38,20])]);
+
+    let wrap = find_simple_coloring(&board)
+        .into_iter()
+        .find(|pattern| pattern.rule == ColoringRule::Wrap)
+        .unwrap();
+    assert_eq!(wrap.eliminations, [0,20,40]);
+
+    let updated_board = SimpleColoring::new().apply(&board).unwrap();
+
+    for idx in [0,20,40] {
+        assert!(!updated_board.cells[idx].contains(5));
+    }
+    assert!(updated_board.cells[4].contains(5));
+    assert!(updated_board.cells[38].contains(5));
+}
+
+#[test]
+fn test_multi_coloring() {
+    // Two separate clusters: row0 col0 / row0 col4 and row1 col1 /
+    // row5 col1.  row0 col0 and row1 col1 share a box so can't both
+    // be 5, so one of row0 col4 and row5 col1 is.
+    let board = board_with_links(5, &[(&Board::rows()[0], &[0,4]),
+                                      (&Board::columns()[1], &[10,46])]);
+
+    assert!(find_simple_coloring(&board).is_empty());
+
+    let patterns = find_multi_coloring(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].rule, ColoringRule::MultiTrap);
+    assert_eq!(patterns[0].eliminations, [49]);
+
+    let updated_board = MultiColoring::new().apply(&board).unwrap();
+
+    assert!(!updated_board.cells[49].contains(5));
+    assert!(updated_board.cells[40].contains(5));
+}
+
+#[test]
+fn test_coloring_on_loaded_boards() {
+    for (strategy, puzzle, solution) in [
+        (SimpleColoring::new(),
+         "..5.14.698.........1...32..4....5.1..2..4893.9...2.........9.4.........63.47..8..",
+         "235814769849267153617593284483975612721648935956321478562189347178432596394756821"),
+        (MultiColoring::new(),
+         "..8..37.57..6...2.2.....1...81...4.....89.6..97.4.1...1.62........346..1........2",
+         "468123795719654823253978146681732459342895617975461238136289574527346981894517362"),
+    ] {
+        assert_progress(strategy, puzzle, solution);
+    }
+}
+
+#[test]
+fn test_medusa_two_colors_in_a_cell() {
+    // row0 col0 can only be 1 or 2, and 1 and 2 can only go in row0
+    // col0 or row0 col4 in row 0.  Whichever is in row0 col0, the
+    // other is in row0 col4, so nothing else can be.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    for idx in [1,2,3,5,6,7,8] {
+        board.cells[idx].remove(1).unwrap();
+        board.cells[idx].remove(2).unwrap();
+    }
+
+    assert_eq!(medusa_clusters(&board), [[vec![(0,1),(4,2)], vec![(0,2),(4,1)]]]);
+
+    let patterns = find_medusa(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].rule, 3);
+    assert_eq!(patterns[0].eliminations, (3..=9).map(|digit| (4, digit)).collect::<Vec<_>>());
+
+    let updated_board = Medusa::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[4], Cell::from_digits([1,2]));
+    assert_eq!(updated_board.cells[0], Cell::from_digits([1,2]));
+}
+
+#[test]
+fn test_medusa_on_loaded_boards() {
+    for (puzzle, solution) in [
+        ("6.9.1.7..5.........7..9.8.1..13....78.....5.3....87..9..48..9.....75.6..98.......",
+         "629518734518473296473692851291365487867941523345287169754836912132759648986124375"),
+        ("5...............19627....48...25......4..3......1.672..9..28..74.......6..54...9.",
+         "519834672348672519627915348761259834254783961983146725196328457432597186875461293"),
+    ] {
+        assert_progress(Medusa::new(), puzzle, solution);
+    }
+}
+
+#[test]
+fn test_x_cycle_continuous() {
+    // 5 can only go in columns 0 and 4 of rows 0 and 4, which makes a
+    // loop with strong links along the rows and weak links down the
+    // columns.
+    let board = board_with_links(5, &[(&Board::rows()[0], &[0,4]),
+                                      (&Board::rows()[4], &[36,40])]);
+
+    // Each cell it removes 5 from also starts a discontinuous loop
+    // of its own, eg row1 col0 - row0 col0 = row0 col4 - row4 col4 =
+    // row4 col0 - row1 col0.
+    let patterns = find_x_cycles(&board);
+    assert_eq!(patterns.len(), 15);
+    assert_eq!(patterns.iter().filter(|pattern| pattern.kind == XCycleKind::DiscontinuousWeak).count(), 14);
+
+    let continuous = patterns
+        .into_iter()
+        .find(|pattern| pattern.kind == XCycleKind::Continuous)
+        .unwrap();
+    assert_eq!(continuous.path, [0,4,40,36,0]);
+    assert_eq!(continuous.strong, [true,false,true,false]);
+    assert_eq!(continuous.eliminations, [9,13,18,22,27,31,45,49,54,58,63,67,72,76]);
+
+    let updated_board = XCycle::new().apply(&board).unwrap();
+
+    for idx in [9,13,18,22,27,31,45,49,54,58,63,67,72,76] {
+        assert!(!updated_board.cells[idx].contains(5));
+    }
+    for idx in [0,4,36,40] {
+        assert!(updated_board.cells[idx].contains(5));
+    }
+}
+
+#[test]
+fn test_x_cycle_on_loaded_boards() {
+    for (puzzle, solution) in [
+        ("..6.3....4..5.6...7.8....9...31...7.1......28...7.23......8....3.4.6....87......5",
+         "526839714491576832738421596243158679157693428689742351965287143314965287872314965"),
+        ("....9.5.......4.6.....21...5.....79..91.3...6..2.....3.19..63.4.68....7...59....1",
+         "384697512127354869956821437543162798891735246672489153219576384468213975735948621"),
+        ("..74...3..61........4...9.6..53....1.7.1....4....9.2...4.2..3.......4.8..2.7.....",
+         "597426138261983547834571926985342671372165894416897253748219365159634782623758419"),
+    ] {
+        assert_progress(XCycle::new(), puzzle, solution);
+    }
+}
+
+#[test]
+fn test_xy_chain() {
+    // Four bivalue cells in a loop round rows 0 and 4 and columns 0
+    // and 4.  Any two neighbours in the loop end a chain through the
+    // other two.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[4] = Cell::from_digits([2,3]);
+    board.cells[40] = Cell::from_digits([3,4]);
+    board.cells[36] = Cell::from_digits([4,1]);
+
+    let patterns = find_xy_chains(&board);
+    assert_eq!(patterns.iter().map(|pattern| pattern.digit).collect::<Vec<_>>(), [1,2,3,4]);
+
+    assert_eq!(patterns[0].chain, [(0,1),(0,2),(4,2),(4,3),(40,3),(40,4),(36,4),(36,1)]);
+    assert_eq!(patterns[0].eliminations, [9,18,27,45,54,63,72]);
+
+    let updated_board = XYChain::new().apply(&board).unwrap();
+
+    for idx in [9,18,27,45,54,63,72] {
+        assert!(!updated_board.cells[idx].contains(1));
+    }
+    for idx in [1,2,3,5,6,7,8] {
+        assert!(!updated_board.cells[idx].contains(2));
+    }
+}
+
+#[test]
+fn test_xy_chain_on_loaded_boards() {
+    for (puzzle, solution) in [
+        ("8....27.52..3...1.....5.4.8.....45...6.8....4..7.1.9..38...6...64....8....1......",
+         "819462735254387619736951428198734562563829174427615983385196247642573891971248356"),
+        (".........7.2.......3.8.671.258.6........8........974......3..54.15..9.2......4...",
+         "196745238782913645534826719258461397947382561361597482679238154415679823823154976"),
+        ("...479.......6.9.75..2.....31..5.....5..8.6....87..5...94..7..6..........23....94",
+         "286479315431865927579231468312654789957183642648792531894327156165948273723516894"),
+    ] {
+        assert_progress(XYChain::new(), puzzle, solution);
+    }
+}
+
+#[test]
+fn test_aic() {
+    // (5)row0 col0 = (5)row0 col4 - (5)row4 col4 = (5)row4 col2, so
+    // one of the ends is a 5.
+    let board = board_with_links(5, &[(&Board::rows()[0], &[0,4]),
+                                      (&Board::rows()[4], &[38,40])]);
+
+    let patterns = find_aics(&board, Aic::DEFAULT_MAX_LENGTH);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, AicType::Type1);
+    assert_eq!(patterns[0].chain, [(0,5),(4,5),(40,5),(38,5)]);
+    assert_eq!(patterns[0].eliminations, [(11,5),(20,5),(27,5),(45,5)]);
+
+    let updated_board = Aic::new().apply(&board).unwrap();
+
+    for idx in [11,20,27,45] {
+        assert!(!updated_board.cells[idx].contains(5));
+    }
+
+    // The chain is four candidates long.
+    assert!(find_aics(&board, 3).is_empty());
+    assert_eq!(Aic::with_max_length(3).apply(&board).unwrap(), board);
+}
+
+#[test]
+fn test_aic_type_2() {
+    // (1)row0 col0 = (2)row0 col0 - (2)row1 col1 = (2)row0 col1, and
+    // the ends see each other, so row0 col0 can't be 2 and row0 col1
+    // can't be 1.
+    let mut board = board_with_links(2, &[(&Board::columns()[1], &[1,10])]);
+    board.cells[0] = Cell::from_digits([1,2]);
+
+    let type_2 = find_aics(&board, Aic::DEFAULT_MAX_LENGTH)
+        .into_iter()
+        .find(|pattern| pattern.chain == [(0,1),(0,2),(10,2),(1,2)])
+        .unwrap();
+    assert_eq!(type_2.kind, AicType::Type2);
+    assert_eq!(type_2.eliminations, [(0,2),(1,1)]);
+
+    let updated_board = Aic::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[0], Cell::Solved(1));
+}
+
+#[test]
+fn test_aic_on_loaded_boards() {
+    for (puzzle, solution) in [
+        ("...92.....5...31.2.......34..5....7...2.9...5.3.7....9.1.47..2.3....97...29..8..1",
+         "843921567657843192291567834985132476172694385436785219518476923364219758729358641"),
+        ("..943...55.4...8..6...8....3..1.....8...2...92..8.3..1....7..6...7..2...9...4..52",
+         "789436125534219876612785493396154287841627539275893641123578964457962318968341752"),
+    ] {
+        assert_progress(Aic::new(), puzzle, solution);
+    }
+}
+
+#[test]
+fn test_almost_locked_sets() {
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[1] = Cell::from_digits([2,3]);
+
+    let sets = almost_locked_sets(&board);
+
+    for (cells, digits) in [(vec![0], vec![1,2]), (vec![1], vec![2,3]), (vec![0,1], vec![1,2,3])] {
+        assert!(sets.contains(&AlmostLockedSet { cells, digits }));
+    }
+
+    // Cells 0 and 1 are in both row 0 and box 0, but only listed once.
+    assert_eq!(sets.iter().filter(|set| set.cells == [0,1]).count(), 1);
+    assert!(sets.iter().all(|set| set.digits.len() == set.cells.len() + 1));
+}
+
+#[test]
+fn test_als_on_loaded_boards() {
+    for (strategy, puzzle, solution) in [
+        (AlsXz::new(),
+         ".......3.5.7.4......1.8...5......59.7......8..28.5.3.64..2..6...8.7......5...6..9",
+         "862571934597643821341982765614328597735469182928157346479235618186794253253816479"),
+        (AlsXz::new(),
+         "7......488..65......2........68..39.1..........7...1.2.1.9.75...3.1..4....9..6...",
+         "765231948891654723342798615456812397123579864987463152214987536638125479579346281"),
+        (AlsXyWing::new(),
+         "...8..4...3...96..1.......9...2..1...751..3..........7..3..4.614.9.5...2.6...89..",
+         "957862413832419675146537289398275146275146398614983527523794861489651732761328954"),
+        (AlsXyWing::new(),
+         ".3.........4...6...9..67...9.3.....5...5..7.8....1.34.1..35..2..2..7.4..369..2..7",
+         "236185974754293681891467253983724165412536798675918342147359826528671439369842517"),
+    ] {
+        assert_progress(strategy, puzzle, solution);
+    }
+}
+
+#[test]
+fn test_als_xz_doubly_linked() {
+    let solution = "528974163941236857673518492294683571165742938837159246789461325352897614416325789";
+    let board = stuck_board("..897..6.941.......7...8..22.....57..6....9.....1.9.....9..13.......7..4...3.....",
+                            strategies_before("ALS-XZ"));
+
+    let patterns = find_als_xz(&board);
+    assert!(patterns.iter().any(|pattern| pattern.restricted.len() == 2));
+
+    let updated_board = AlsXz::new().apply(&board).unwrap();
+
+    assert_ne!(updated_board, board);
+    assert_consistent(&updated_board, solution);
+}
+
+#[test]
+fn test_sue_de_coq() {
+    // row0 col0 and row0 col1 can only be 1234 between them.  With
+    // row0 col5 holding 12 and row2 col0 holding 34, those four cells
+    // have to hold all four digits, 1 and 2 somewhere in row 0 and 3
+    // and 4 somewhere in box 0.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2,3]);
+    board.cells[1] = Cell::from_digits([2,3,4]);
+    board.cells[5] = Cell::from_digits([1,2]);
+    board.cells[18] = Cell::from_digits([3,4]);
+
+    let patterns = find_sue_de_coqs(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].cells, [0,1]);
+    assert_eq!(patterns[0].digits, [1,2,3,4]);
+    assert_eq!(patterns[0].line_cells, [5]);
+    assert_eq!(patterns[0].line_digits, [1,2]);
+    assert_eq!(patterns[0].box_cells, [18]);
+    assert_eq!(patterns[0].box_digits, [3,4]);
+
+    let updated_board = SueDeCoq::new().apply(&board).unwrap();
+
+    for idx in [2,3,4,6,7,8] {
+        assert!(!updated_board.cells[idx].contains(1));
+        assert!(!updated_board.cells[idx].contains(2));
+    }
+    for idx in [2,9,10,11,19,20] {
+        assert!(!updated_board.cells[idx].contains(3));
+        assert!(!updated_board.cells[idx].contains(4));
+    }
+    assert_eq!(updated_board.cells[9], Cell::from_digits([1,2,5,6,7,8,9]));
+}
+
+#[test]
+fn test_sue_de_coq_on_loaded_boards() {
+    for (puzzle, solution) in [
+        (".3579.....7.......2..6.18..........44...3..9.5..4.71.6..19.....3......2..28...5..",
+         "135798462876243951294651873763519284412836795589427136651972348347185629928364517"),
+        ("...94..528....5...9.5....4.31.58.7.......9..6...7........29...176.8..2....16.....",
+         "136947852847125963925368147314586729572419386689732415458293671763851294291674538"),
+    ] {
+        assert_progress(SueDeCoq::new(), puzzle, solution);
+    }
+}
+
+#[test]
+fn test_death_blossom_on_loaded_boards() {
+    for (puzzle, solution) in [
+        ("..7.1.6...2......34....75..........2.369....17...289.....8.4..6.........57.....3.",
+         "957413628128659473463287519895761342236945781741328965319874256684532197572196834"),
+        ("..9..8.2.....6.3...2..7..4......65.1.5.....34..4.9.....48..296.........3.32......",
+         "369418725417265389825379146273846591956721834184593672548132967791684253632957418"),
+    ] {
+        // Turning death blossoms on puts them in just before the
+        // forcing chains.
+        let board = stuck_board(puzzle, strategies_before("CellForcingChain"));
+
+        let (updated_board, details) = DeathBlossom::new().deduce(&board).unwrap();
+
+        assert_ne!(updated_board, board);
+        assert!(!details.is_empty());
+        assert_consistent(&updated_board, solution);
+
+        let solved = Solver::new()
+            .death_blossom(DeathBlossom::DEFAULT_MAX_SIZE)
+            .solve(&Board::from_str(puzzle).unwrap());
+        assert!(solved.steps.iter().any(|step| step.strategy == "DeathBlossom"));
+    }
+}
+
+#[test]
+fn test_death_blossom_max_size() {
+    // The only death blossom here needs a petal of three cells.
+    let solution = "915487632684239715237651948726893154543176289891542376159764823462318597378925461";
+    let board = stuck_board("...4....2....397.523..51..8...8.......3...2...91.4..7.15........6.3...9.....25..1",
+                            strategies_before("CellForcingChain"));
+
+    assert!(find_death_blossoms(&board, 2).is_empty());
+    assert_eq!(DeathBlossom::with_max_size(2).apply(&board).unwrap(), board);
+
+    let patterns = find_death_blossoms(&board, 3);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].stem, 1);
+    assert_eq!(patterns[0].digit, 4);
+    assert_eq!(patterns[0].petals.iter().map(|(digit, petal)| (*digit, petal.cells.clone())).collect::<Vec<_>>(),
+               [(1, vec![6,78]), (7, vec![73,78,79])]);
+    assert_eq!(patterns[0].eliminations, [(74,4)]);
+
+    let updated_board = DeathBlossom::with_max_size(3).apply(&board).unwrap();
+
+    assert!(!updated_board.cells[74].contains(4));
+    assert_consistent(&updated_board, solution);
+}
+
+#[test]
+fn test_nishio_on_notes_board() {
+    // The first deduction for the board in notes.txt: a 7 at row7 col0
+    // leaves nowhere for the 7 in column 6.
+    let board = stuck_board("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8...9..36..",
+                            strategies_before("Nishio"));
+
+    let patterns = find_nishio(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].cell, 63);
+    assert_eq!(patterns[0].digit, 7);
+    assert_eq!(patterns[0].substeps, 6);
+    assert_eq!(patterns[0].contradiction,
+               Some(Contradiction::new("HiddenSingle").with_group(&Board::columns()[6]).with_digit(7)));
+
+    let updated_board = Nishio::new().apply(&board).unwrap();
+
+    assert!(!updated_board.cells[63].contains(7));
+    assert!(updated_board.valid());
+}
+
+#[test]
+fn test_cell_forcing_chain() {
+    // If row0 col0 is 1, row0 col1 is 3.  If it is 2, row1 col0 is 3.
+    // Either way the rest of box 0 can't be 3.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[1] = Cell::from_digits([1,3]);
+    board.cells[9] = Cell::from_digits([2,3]);
+
+    let pattern = find_cell_forcing_chains(&board)
+        .into_iter()
+        .find(|pattern| pattern.branches == [(0,1),(0,2)])
+        .unwrap();
+    for idx in [2,10,11,18,19,20] {
+        assert!(pattern.eliminations.contains(&(idx, 3)));
+    }
+    assert!(pattern.placements.is_empty());
+
+    let updated_board = CellForcingChain::new().apply(&board).unwrap();
+
+    for idx in [2,10,11,18,19,20] {
+        assert!(!updated_board.cells[idx].contains(3));
+    }
+    assert_eq!(updated_board.cells[0], Cell::from_digits([1,2]));
+}
+
+#[test]
+fn test_forcing_chains_on_loaded_boards() {
+    for (strategy, puzzle, solution) in [
+        (CellForcingChain::new(),
+         "....9..1.2..3....45.7....9..3.82.........41..8.5..6....8..6...2.5.27..6........79",
+         "348792615296351784517648293431827956679534128825916437783169542954273861162485379"),
+        (CellForcingChain::new(),
+         "..9..7......63..51...8.1.7.16.2....9..4.1....9.7..3.8.395...........28...2.3.....",
+         "419527368872634951536891274163278549284915736957463182395186427641752893728349615"),
+        (UnitForcingChain::new(),
+         "7...1..9.8.2..3.......9...61.5.8..............3...5.2.4.......2..7.561.....2..45.",
+         "763518294892643571541792386125987643678324915934165827459831762287456139316279458"),
+        (UnitForcingChain::new(),
+         "...74.9..2.83.....493.........5..81.....81........3..5.5....4..1..95.6.3.2...61..",
+         "561748932278319546493625781632597814745281369819463275356172498187954623924836157"),
+    ] {
+        assert_progress(strategy, puzzle, solution);
+    }
+}
+
+#[test]
+fn test_backtracker_solves_notes_board() {
+    // The basic strategies get stuck on the board from notes.txt, so
+    // it takes a lot of guessing.
+    let board = Board::from_str("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8...9..36..").unwrap();
+    let backtracker = Backtracker::with_solver(Solver::with_strategies(vec![RemoveSolvedFromNeighbors::new(), HiddenSingle::new()]));
+
+    let solution = backtracker.with_max_depth(81).solve(&board);
+
+    assert_eq!(solution.state, SolveState::Solved);
+    assert_eq!(solution.board, Board::from_str("583427169974136528216859374792364851351298746648715293865971432137642985429583617").unwrap());
+    assert!(!solution.guesses.is_empty());
+    assert_eq!(solution.guesses.last().unwrap().state, SolveState::Solved);
+
+    let lines = guess_lines(&solution.guesses);
+    assert!(lines[0].starts_with("guessed "));
+    assert!(lines.iter().any(|line| line.starts_with("  guessed ")));
+}
+
+#[test]
+fn test_backtracker_respects_max_depth() {
+    let board = Board::from_str("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8...9..36..").unwrap();
+    let backtracker = Backtracker::with_solver(Solver::with_strategies(vec![RemoveSolvedFromNeighbors::new(), HiddenSingle::new()]));
+
+    let solution = backtracker.with_max_depth(0).solve(&board);
+
+    assert_eq!(solution.state, SolveState::Stuck);
+    assert!(solution.guesses.is_empty());
+}
+
+#[test]
+fn test_backtracker_reports_invalid() {
+    // Three cells in a row that can only be 1 or 2.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[1] = Cell::from_digits([1,2]);
+    board.cells[2] = Cell::from_digits([1,2]);
+    let backtracker = Backtracker::with_solver(Solver::with_strategies(vec![RemoveSolvedFromNeighbors::new(), HiddenSingle::new()]));
+
+    let solution = backtracker.solve(&board);
+
+    assert_eq!(solution.state, SolveState::Invalid);
+    assert_eq!(solution.guesses.iter().map(|guess| (guess.cell, guess.digit)).collect::<Vec<_>>(), [(0,1),(0,2)]);
+    assert!(solution.guesses.iter().all(|guess| guess.state == SolveState::Invalid));
+}
+
+#[test]
+fn test_backtracker_guesses_on_stuck_board() {
+    // The classic board's solution with a rectangle of 1s and 3s
+    // blanked out, which leaves it with two solutions.
+    let board = stuck_board("53467891267219534819834256785976.42.42685.79.713924856961537284287419635345286179",
+                            get_strategies());
+
+    let solution = Backtracker::with_solver(Solver::new()).solve(&board);
+
+    assert_eq!(solution.state, SolveState::Solved);
+    assert_eq!(solution.board.cells[32], Cell::Solved(1));
+    assert_eq!(guess_lines(&solution.guesses), ["guessed 1 at row3 col5: Solved"]);
+}
+
+#[test]
+fn test_backtracker_keeps_solver_options() {
+    // The unique rectangle can only place the 3 if the solver it was
+    // given is allowed to assume uniqueness.
+    let mut board = Board::new();
+    for idx in [0,3,9] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    board.cells[12] = Cell::from_digits([1,2,3]);
+
+    let solution = Backtracker::with_solver(Solver::with_strategies(vec![UniqueRectangle::new()]))
+        .with_max_depth(0)
+        .solve(&board);
+    assert_eq!(solution.board, board);
+
+    let solution = Backtracker::with_solver(Solver::with_strategies(vec![UniqueRectangle::new()]).assume_unique(true))
+        .with_max_depth(0)
+        .solve(&board);
+    assert_eq!(solution.board.cells[12], Cell::Solved(3));
+}
+
+#[test]
+fn test_guess() {
+    // The same two-solution board as above: the strategies are stuck,
+    // so Guess places the first digit of the solution it finds.
+    let puzzle = "53467891267219534819834256785976.42.42685.79.713924856961537284287419635345286179";
+    let board = stuck_board(puzzle, get_strategies());
+
+    let updated_board = Guess::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[32], Cell::Solved(1));
+    assert_eq!(Guess::new().explain(&board), ["guessed 1 at row3 col5: Solved"]);
+
+    // With no guesses allowed there is nothing to place.
+    let guess = Guess::with_backtracker(Backtracker::with_solver(Solver::new()).with_max_depth(0));
+    assert_eq!(guess.apply(&board).unwrap(), board);
+
+    // Added to the end of a solver, Guess finishes the board.
+    let mut strategies = get_strategies();
+    strategies.push(Guess::new());
+    let solution = Solver::with_strategies(strategies).solve(&Board::from_str(puzzle).unwrap());
+
+    assert_eq!(solution.state, SolveState::Solved);
+    assert_eq!(solution.steps.iter().filter(|step| step.strategy == "Guess").count(), 1);
+    assert_consistent(&solution.board, "534678912672195348198342567859761423426853791713924856961537284287419635345286179");
+}
+
+#[test]
+fn test_guess_reports_invalid() {
+    // Three cells in a row that can only be 1 or 2.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[1] = Cell::from_digits([1,2]);
+    board.cells[2] = Cell::from_digits([1,2]);
+
+    // The full solver sees the problem without having to guess.
+    let contradiction = Guess::new().apply(&board).unwrap_err();
+
+    assert_eq!(contradiction.strategy, "Guess");
+    assert_eq!(contradiction.cell, None);
+
+    // The basic strategies need a guess in the first cell to see it.
+    let backtracker = Backtracker::with_solver(Solver::with_strategies(vec![RemoveSolvedFromNeighbors::new(), HiddenSingle::new()]));
+    let contradiction = Guess::with_backtracker(backtracker).apply(&board).unwrap_err();
+
+    assert_eq!(contradiction.strategy, "Guess");
+    assert_eq!(contradiction.cell, Some(0));
+}
+
+#[test]
+fn test_unique_rectangle_type_1() {
+    // Three corners of the rectangle in rows 0 and 1 and columns 0
+    // and 3 are left with just 1 and 2, so the fourth must be 3.
+    let mut board = Board::new();
+    for idx in [0,3,9] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    board.cells[12] = Cell::from_digits([1,2,3]);
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Type1);
+    assert_eq!(patterns[0].cells, [0,3,9,12]);
+    assert_eq!(patterns[0].eliminations, [(12,1),(12,2)]);
+
+    let updated_board = UniqueRectangle::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[12], Cell::Solved(3));
+}
+
+#[test]
+fn test_unique_rectangle_type_2() {
+    // The roofs in row 1 both have an extra 3, so one of them is 3
+    // and the rest of row 1 can't be.
+    let mut board = Board::new();
+    for idx in [0,3] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    for idx in [9,12] {
+        board.cells[idx] = Cell::from_digits([1,2,3]);
+    }
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Type2);
+    assert_eq!(patterns[0].cells, [0,3,9,12]);
+    assert_eq!(patterns[0].eliminations, [(10,3),(11,3),(13,3),(14,3),(15,3),(16,3),(17,3)]);
+
+    let updated_board = UniqueRectangle::new().apply(&board).unwrap();
+
+    for idx in [10,11,13,14,15,16,17] {
+        assert!(!updated_board.cells[idx].contains(3));
+    }
+    assert!(updated_board.cells[9].contains(3));
+}
+
+#[test]
+fn test_unique_rectangle_type_3() {
+    // One of the roofs in row 1 is 3 or 4, which makes a naked pair
+    // with row1 col1, so the rest of row 1 can't be 3 or 4.
+    let mut board = Board::new();
+    for idx in [0,3] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    board.cells[9] = Cell::from_digits([1,2,3]);
+    board.cells[12] = Cell::from_digits([1,2,4]);
+    board.cells[10] = Cell::from_digits([3,4]);
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Type3);
+    assert_eq!(patterns[0].cells, [0,3,9,12]);
+    assert_eq!(patterns[0].eliminations,
+               [(11,3),(11,4),(13,3),(13,4),(14,3),(14,4),(15,3),(15,4),(16,3),(16,4),(17,3),(17,4)]);
+
+    let updated_board = UniqueRectangle::new().apply(&board).unwrap();
+
+    for idx in [11,13,14,15,16,17] {
+        assert!(!updated_board.cells[idx].contains(3));
+        assert!(!updated_board.cells[idx].contains(4));
+    }
+    assert_eq!(updated_board.cells[10], Cell::from_digits([3,4]));
+}
+
+#[test]
+fn test_unique_rectangle_type_4() {
+    // The roofs are the only places for 1 in row 1, so one of them is
+    // 1 and neither can be 2.
+    let mut board = board_with_links(1, &[(&Board::rows()[1], &[9,12])]);
+    for idx in [0,3] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    board.cells[9] = Cell::from_digits([1,2,5]);
+    board.cells[12] = Cell::from_digits([1,2,6]);
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Type4);
+    assert_eq!(patterns[0].cells, [0,3,9,12]);
+    assert_eq!(patterns[0].eliminations, [(9,2),(12,2)]);
+
+    let updated_board = UniqueRectangle::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[9], Cell::from_digits([1,5]));
+    assert_eq!(updated_board.cells[12], Cell::from_digits([1,6]));
+}
+
+#[test]
+fn test_unique_rectangle_type_5() {
+    // Diagonal corners both have an extra 7, so one of them is 7 and
+    // nothing that sees both can be.
+    let mut board = Board::new();
+    for idx in [0,12] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    for idx in [3,9] {
+        board.cells[idx] = Cell::from_digits([1,2,7]);
+    }
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Type5);
+    assert_eq!(patterns[0].cells, [0,3,9,12]);
+    assert_eq!(patterns[0].eliminations, [(1,7),(2,7),(13,7),(14,7)]);
+
+    // With a third corner holding the extra 7, only the cells that
+    // see all three are left.
+    board.cells[12] = Cell::from_digits([1,2,7]);
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Type5);
+    assert_eq!(patterns[0].eliminations, [(13,7),(14,7)]);
+
+    let updated_board = UniqueRectangle::new().apply(&board).unwrap();
+
+    for idx in [13,14] {
+        assert!(!updated_board.cells[idx].contains(7));
+    }
+    assert!(updated_board.cells[1].contains(7));
+}
+
+#[test]
+fn test_unique_rectangle_type_6() {
+    // Diagonal corners have extras, and 1 only goes in the rectangle
+    // in rows 0 and 1.  The 1s have to be on one diagonal or the
+    // other, and the corners with extras can't make the deadly
+    // pattern, so they can't be 1.
+    let mut board = board_with_links(1, &[(&Board::rows()[0], &[0,3]),
+                                          (&Board::rows()[1], &[9,12])]);
+    for idx in [0,12] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    board.cells[3] = Cell::from_digits([1,2,8]);
+    board.cells[9] = Cell::from_digits([1,2,9]);
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Type6);
+    assert_eq!(patterns[0].cells, [0,3,9,12]);
+    assert_eq!(patterns[0].eliminations, [(3,1),(9,1)]);
+
+    let updated_board = UniqueRectangle::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[3], Cell::from_digits([2,8]));
+    assert_eq!(updated_board.cells[9], Cell::from_digits([2,9]));
+}
+
+#[test]
+fn test_hidden_unique_rectangle() {
+    // row0 col0 is left with 1 and 2, and the only places for 1 in
+    // row 1 and column 3 are in the rectangle, so the opposite corner
+    // can't be 2.
+    let mut board = board_with_links(1, &[(&Board::rows()[1], &[9,12]),
+                                          (&Board::columns()[3], &[3,12])]);
+    board.cells[0] = Cell::from_digits([1,2]);
+
+    let patterns = find_unique_rectangles(&board);
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].kind, UniqueRectangleType::Hidden);
+    assert_eq!(patterns[0].eliminations, [(12,2)]);
+
+    let updated_board = HiddenUniqueRectangle::new().apply(&board).unwrap();
+
+    assert!(!updated_board.cells[12].contains(2));
+    assert_eq!(UniqueRectangle::new().apply(&board).unwrap(), board);
+}
+
+#[test]
+fn test_solver_only_assumes_uniqueness_when_told() {
+    let mut board = Board::new();
+    for idx in [0,3,9] {
+        board.cells[idx] = Cell::from_digits([1,2]);
+    }
+    board.cells[12] = Cell::from_digits([1,2,3]);
+
+    let solution = Solver::with_strategies(vec![UniqueRectangle::new()]).solve(&board);
+
+    assert_eq!(solution.state, SolveState::Stuck);
+    assert_eq!(solution.board, board);
+
+    let solution = Solver::with_strategies(vec![UniqueRectangle::new()]).assume_unique(true).solve(&board);
+
+    assert_eq!(solution.steps.len(), 1);
+    assert_eq!(solution.board.cells[12], Cell::Solved(3));
+}
+
+#[test]
+fn test_unique_rectangles_on_loaded_boards() {
+    for (strategy, puzzle, solution) in [
+        (UniqueRectangle::new(),
+         "..943...55.4...8..6...8....3..1.....8...2...92..8.3..1....7..6...7..2...9...4..52",
+         "789436125534219876612785493396154287841627539275893641123578964457962318968341752"),
+        (UniqueRectangle::new(),
+         "...9...72...2.74.5..3...1..58.......3.1....89......31.8...73.5.7...5........4...1",
+         "154938672698217435273465198587391246361724589429586317812673954746159823935842761"),
+        (UniqueRectangle::new(),
+         ".........7.2.......3.8.671.258.6........8........974......3..54.15..9.2......4...",
+         "196745238782913645534826719258461397947382561361597482679238154415679823823154976"),
+        (HiddenUniqueRectangle::new(),
+         "5...............19627....48...25......4..3......1.672..9..28..74.......6..54...9.",
+         "519834672348672519627915348761259834254783961983146725196328457432597186875461293"),
+    ] {
+        assert!(strategy.requires_uniqueness());
+        assert_progress(strategy, puzzle, solution);
+    }
+}
+
+#[test]
+fn test_bug() {
+    let solution = "391627458682415937574398126468231795719856342253749861146583279925174683837962514";
+    let board = stuck_board(".9.6.7.5...2..5.....4.9....4.8......7..856.....3....6......3.7..2.1....3......51.",
+                            strategies_before("BUG+1"));
+
+    let unsolved = (0..81)
+        .filter(|idx| matches!(board.cells[*idx], Cell::Unsolved(_)))
+        .collect::<Vec<usize>>();
+    assert_eq!(unsolved.iter().filter(|idx| board.cells[**idx].count() != 2).count(), 1);
+
+    let pattern = find_bug(&board).unwrap();
+    assert_eq!(board.cells[pattern.cell].count(), 3);
+
+    let updated_board = Bug::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[pattern.cell], Cell::Solved(pattern.digit));
+    assert_consistent(&updated_board, solution);
+    assert!(Bug::new().requires_uniqueness());
+
+    let solution = Solver::with_strategies(vec![Bug::new()]).solve(&board);
+    assert_eq!(solution.board, board);
+}
+
+#[test]
+fn test_bug_needs_two_places_everywhere() {
+    // row0 col0 is the only cell with three digits and every other
+    // unsolved cell has two, with 1 in three places in each of its
+    // groups.  But 2, 3 and 4 have just one place in most of their
+    // groups, so this isn't a grave and 1 can't be placed.
+    let mut board = Board::from_str("391627458682415937574398126468231795719856342253749861146583279925174683837962514").unwrap();
+    board.cells[0] = Cell::from_digits([1,2,3]);
+    for idx in [3,6,27,54,10,20] {
+        board.cells[idx] = Cell::from_digits([1,4]);
+    }
+
+    assert_eq!(find_bug(&board), None);
+    assert_eq!(Bug::new().apply(&board).unwrap(), board);
+}
+
+#[test]
+fn test_hidden_single() {
+    // Only cell 4 in the first row can hold a 5.
+    let mut board = Board::new();
+    for idx in Board::row_neighbors(4) {
+        board.cells[idx] = Cell::from_digits([1,2,3,4,6,7,8,9]);
+    }
+
+    let updated_board = HiddenSingle::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[4], Cell::Solved(5));
+    for idx in (0..81).filter(|idx| *idx != 4) {
+        assert_eq!(updated_board.cells[idx], board.cells[idx]);
+    }
+}
+
+#[test]
+fn test_hidden_single_on_loaded_board() {
+    let board_in = Board::from_str("53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79").unwrap();
+    let solution = Board::from_str("534678912672195348198342567859761423426853791713924856961537284287419635345286179").unwrap();
+    let board = RemoveSolvedFromNeighbors::new().apply(&board_in).unwrap();
+
+    // Only cell 5 in the top row can hold an 8, even though the cell
+    // itself could still be 2, 4, 6 or 8.
+    assert_eq!(board.cells[5], Cell::from_digits([2,4,6,8]));
+
+    let updated_board = HiddenSingle::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board.cells[5], Cell::Solved(8));
+    assert!(updated_board.valid());
+
+    // Every cell that was solved matches the known solution.
+    for idx in 0..81 {
+        if let Cell::Solved(digit) = updated_board.cells[idx] {
+            assert_eq!(Cell::Solved(digit), solution.cells[idx], "at index {}", idx);
+        }
+    }
+}
+
+#[test]
+fn test_hidden_single_reports_contradiction() {
+    // Nothing in the first column can hold a 3.
+    let mut board = Board::new();
+    for idx in Board::columns()[0].iter() {
+        board.cells[*idx] = Cell::from_digits([1,2,4,5,6,7,8,9]);
+    }
+
+    let contradiction = HiddenSingle::new().apply(&board).unwrap_err();
+
+    assert_eq!(contradiction.strategy, "HiddenSingle");
+    assert_eq!(contradiction.group, Some(Board::columns()[0].clone()));
+    assert_eq!(contradiction.digit, Some(3));
+}
+
+#[test]
+fn test_solver_solves_easy_board() {
+    // The trivial solved board from test_solved with every fourth
+    // cell blanked out.
+    let board = Board::from_str(".234.678.456.891.378.123.562.456.891.678.123.891.345.734.678.126.891.345.123.567.").unwrap();
+
+    let solution = Solver::new().solve(&board);
+
+    assert_eq!(solution.state, SolveState::Solved);
+    assert!(solution.board.solved());
+    assert!(!solution.steps.is_empty());
+}
+
+#[test]
+fn test_solver_solves_classic_board() {
+    let board = Board::from_str("53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79").unwrap();
+
+    let solution = Solver::new().solve(&board);
+
+    assert_eq!(solution.state, SolveState::Solved);
+    assert_eq!(solution.board, Board::from_str("534678912672195348198342567859761423426853791713924856961537284287419635345286179").unwrap());
+}
+
+#[test]
+fn test_solver_explains_steps() {
+    let board = Board::from_str(".5..7.49..76.5....2..1.......831...4......1.8....257...4..6..71.........5..9..6..").unwrap();
+
+    let solution = Solver::new().solve(&board);
+
+    let step = solution.steps.iter().find(|step| step.strategy == "Swordfish").unwrap();
+    assert!(!step.details.is_empty());
+    assert!(step.details[0].contains("is confined to"));
+}
+
+#[test]
+fn test_solver_gets_stuck() {
+    let board = Board::from_str("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8...9..36..").unwrap();
+
+    let solution = Solver::with_strategies(vec![RemoveSolvedFromNeighbors::new()]).solve(&board);
+
+    assert_eq!(solution.state, SolveState::Stuck);
+    assert_eq!(solution.steps.len(), 1);
+    assert_eq!(solution.steps[0].strategy, "RemoveSolvedFromNeighbors");
+    assert!(solution.board.valid());
+}
+
+#[test]
+fn test_solver_reports_invalid() {
+    // 5 at 0 (0,0) and at 72 (8,0)
+    let board = Board::from_str("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8.5.9..36..").unwrap();
+
+    let solution = Solver::new().solve(&board);
+
+    assert_eq!(solution.state, SolveState::Invalid);
+    assert!(solution.steps.is_empty());
+    assert_eq!(solution.contradiction, None);
+}
+
+#[test]
+fn test_remove_solved_reports_contradiction() {
+    // 5 at 0 (0,0) and at 72 (8,0)
+    let board = Board::from_str("5...27..9..41......1..5.3...92.6.8...5......66..7..29.8...7...2.......8.5.9..36..").unwrap();
+
+    let contradiction = RemoveSolvedFromNeighbors::new().apply(&board).unwrap_err();
+
+    assert_eq!(contradiction.strategy, "RemoveSolvedFromNeighbors");
+    assert_eq!(contradiction.cell, Some(72));
+    assert_eq!(contradiction.digit, Some(5));
+}
+
+#[test]
+fn test_naked_pair_ignores_matching_triples() {
+    // Two cells in a row with the same three digits aren't a pair.
+    // This used to trip an assertion in NakedPair.
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2,3]);
+    board.cells[1] = Cell::from_digits([1,2,3]);
+
+    let updated_board = NakedPair::new().apply(&board).unwrap();
+
+    assert_eq!(updated_board, board);
+}
+
+#[test]
+fn test_naked_pair_reports_contradiction() {
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[1] = Cell::from_digits([1,2]);
+    board.cells[8] = Cell::Solved(2);
+
+    let contradiction = NakedPair::new().apply(&board).unwrap_err();
+
+    assert_eq!(contradiction.strategy, "NakedPair");
+    assert_eq!(contradiction.cell, Some(8));
+    assert_eq!(contradiction.digit, Some(2));
+    assert_eq!(contradiction.group, Some(Board::rows()[0].clone()));
+}
+
+#[test]
+fn test_solver_reports_contradiction() {
+    let mut board = Board::new();
+    board.cells[0] = Cell::from_digits([1,2]);
+    board.cells[1] = Cell::from_digits([1,2]);
+    board.cells[2] = Cell::from_digits([1,2]);
+
+    let solution = Solver::new().solve(&board);
+
+    assert_eq!(solution.state, SolveState::Invalid);
+    let contradiction = solution.contradiction.unwrap();
+    assert_eq!(contradiction.strategy, "NakedPair");
+    assert_eq!(contradiction.group, Some(Board::rows()[0].clone()));
+}
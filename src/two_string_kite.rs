@@ -0,0 +1,103 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::cell_names;
+use std::fmt;
+
+// A row and a column that each have only two places for a digit,
+// where one place from each sits in the same box.  Only one of those
+// two can hold the digit, so one of the other ends of the row and
+// column must, and the digit can be removed from every cell that
+// sees both of those ends.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KitePattern {
+    pub digit: usize,
+    pub row: [usize; 2],
+    pub column: [usize; 2],
+    pub eliminations: Vec<usize>,
+}
+
+impl fmt::Display for KitePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is at {} or {} and at {} or {}, with {} and {} sharing a box, so it is removed from {}",
+               self.digit, cell_name(self.row[0]), cell_name(self.row[1]),
+               cell_name(self.column[0]), cell_name(self.column[1]),
+               cell_name(self.row[0]), cell_name(self.column[0]),
+               cell_names(&self.eliminations))
+    }
+}
+
+impl Pattern for KitePattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+// In each kite found, the first cell of the row and column pairs are
+// the ones that share a box.
+pub fn find_kites(board: &Board) -> Vec<KitePattern> {
+    let mut result = Vec::new();
+
+    for digit in 1..=9 {
+        let row_links = Board::rows()
+            .iter()
+            .map(|row| board.places(row, digit))
+            .filter(|places| places.len() == 2)
+            .collect::<Vec<_>>();
+
+        let column_links = Board::columns()
+            .iter()
+            .map(|column| board.places(column, digit))
+            .filter(|places| places.len() == 2)
+            .collect::<Vec<_>>();
+
+        for row_link in &row_links {
+            for column_link in &column_links {
+                // The row and column can't share a cell.
+                if row_link.iter().any(|idx| column_link.contains(idx)) {
+                    continue;
+                }
+
+                for row in [[row_link[0], row_link[1]], [row_link[1], row_link[0]]] {
+                    for column in [[column_link[0], column_link[1]], [column_link[1], column_link[0]]] {
+                        if Board::box_of(row[0]) != Board::box_of(column[0]) {
+                            continue;
+                        }
+
+                        let eliminations = (0..81)
+                            .filter(|idx| Board::sees(*idx, row[1])
+                                    && Board::sees(*idx, column[1])
+                                    && board.cells[*idx].contains(digit))
+                            .collect::<Vec<usize>>();
+
+                        if !eliminations.is_empty() {
+                            result.push(KitePattern { digit, row, column, eliminations });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub struct TwoStringKite;
+
+impl Strategy for TwoStringKite {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(TwoStringKite {})
+    }
+
+    fn name(&self) -> String {
+        String::from("TwoStringKite")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_kites(board))
+    }
+}
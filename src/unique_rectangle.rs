@@ -0,0 +1,292 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::candidate_names;
+use crate::cell_names;
+use crate::combinations;
+use std::collections::BTreeSet;
+use std::fmt;
+
+// The kinds of unique rectangle.  Each one avoids the deadly pattern:
+// four cells in two rows, two columns and two boxes that are all left
+// with just the same two digits, which could then be swapped to give
+// a second solution.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UniqueRectangleType {
+    // Three cells hold just the two digits, so the fourth can't be
+    // either.
+    Type1,
+
+    // Two cells in a line hold just the two digits, and the other two
+    // have a single extra digit, the same in both.  One of them has to
+    // be it, so nothing that sees both can.
+    Type2,
+
+    // Two cells in a line have extra digits, which together with other
+    // cells in a group they share make a naked subset.
+    Type3,
+
+    // Two cells in a line have extra digits, and they are the only
+    // places for one of the two digits in a group they share, so they
+    // can't be the other.
+    Type4,
+
+    // Two or three cells have the same single extra digit, and at
+    // least one of them is not in a line with another, so one has to
+    // be it and nothing that sees all of them can.
+    Type5,
+
+    // Two diagonal cells have extra digits, and one of the two digits
+    // is confined to the rectangle in both of its rows or both of its
+    // columns, so the diagonal cells can't be it.
+    Type6,
+
+    // One cell holds just the two digits, and one of them is confined
+    // to the rectangle in the row and column of the opposite cell, so
+    // the opposite cell can't be the other.
+    Hidden,
+}
+
+// The cells of a rectangle are kept in the order top left, top right,
+// bottom left, bottom right, so flipping bit 0 of a position moves
+// along its row, flipping bit 1 moves along its column, and 3 minus a
+// position is the opposite corner.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UniqueRectanglePattern {
+    pub kind: UniqueRectangleType,
+    pub digits: [usize; 2],
+    pub cells: [usize; 4],
+    pub eliminations: Vec<Candidate>,
+}
+
+impl fmt::Display for UniqueRectanglePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} on {}{} at {} removes {}",
+               self.kind, self.digits[0], self.digits[1],
+               cell_names(&self.cells),
+               candidate_names(&self.eliminations))
+    }
+}
+
+impl Pattern for UniqueRectanglePattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.clone()
+    }
+}
+
+// Every rectangle of unsolved cells in two rows, two columns and two
+// boxes, with each pair of digits all four cells could still be.
+pub fn rectangles(board: &Board) -> Vec<([usize; 4], [usize; 2])> {
+    let rows = Board::rows();
+    let mut result = Vec::new();
+
+    for r in combinations(&[0, 1, 2, 3, 4, 5, 6, 7, 8], 2) {
+        for c in combinations(&[0, 1, 2, 3, 4, 5, 6, 7, 8], 2) {
+            let cells = [rows[r[0]][c[0]], rows[r[0]][c[1]], rows[r[1]][c[0]], rows[r[1]][c[1]]];
+
+            let boxes = cells.iter().map(|idx| Board::box_of(*idx)).collect::<BTreeSet<usize>>();
+            if boxes.len() != 2 || !cells.iter().all(|idx| matches!(board.cells[*idx], Cell::Unsolved(_))) {
+                continue;
+            }
+
+            for digits in combinations(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 2) {
+                if cells.iter().all(|idx| board.cells[*idx].contains(digits[0]) && board.cells[*idx].contains(digits[1])) {
+                    result.push((cells, [digits[0], digits[1]]));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// The places for the digit in a group, as a set for comparing.
+fn places(board: &Board, group: &[usize], digit: usize) -> BTreeSet<usize> {
+    board.places(group, digit).into_iter().collect()
+}
+
+pub fn find_unique_rectangles(board: &Board) -> Vec<UniqueRectanglePattern> {
+    let mut result = Vec::new();
+
+    for (cells, digits) in rectangles(board) {
+        let [a, b] = digits;
+        let extras = cells.map(|idx| {
+            let mut extra = board.cells[idx].digits().into_iter().filter(|digit| *digit != a && *digit != b).collect::<Vec<usize>>();
+            extra.sort_unstable();
+            extra
+        });
+        let extra_cells = (0..4).filter(|i| !extras[*i].is_empty()).collect::<Vec<usize>>();
+
+        let mut push = |kind, eliminations: Vec<Candidate>| {
+            if !eliminations.is_empty() {
+                result.push(UniqueRectanglePattern { kind, digits, cells, eliminations });
+            }
+        };
+
+        // Unsolved cells that see every one of the rectangle cells at
+        // the passed-in positions and could be the digit.
+        let seeing = |positions: &[usize], digit: usize| {
+            (0..81)
+                .filter(|idx| board.cells[*idx].contains(digit)
+                        && positions.iter().all(|i| Board::sees(*idx, cells[*i])))
+                .map(|idx| (idx, digit))
+                .collect::<Vec<Candidate>>()
+        };
+
+        // Every corner holding just the two digits gives a hidden
+        // rectangle a try from the opposite corner.
+        if !extra_cells.is_empty() {
+            for floor in (0..4).filter(|i| extras[*i].is_empty()) {
+                let corner = 3 - floor;
+                let row = &Board::rows()[cells[corner] / 9];
+                let column = &Board::columns()[cells[corner] % 9];
+
+                for (x, y) in [(a, b), (b, a)] {
+                    if places(board, row, x) == BTreeSet::from([cells[corner], cells[corner ^ 1]])
+                        && places(board, column, x) == BTreeSet::from([cells[corner], cells[corner ^ 2]])
+                    {
+                        push(UniqueRectangleType::Hidden, vec![(cells[corner], y)]);
+                    }
+                }
+            }
+        }
+
+        match extra_cells[..] {
+            [i] => push(UniqueRectangleType::Type1, vec![(cells[i], a), (cells[i], b)]),
+
+            [i, j] => {
+                let in_line = j == i ^ 1 || j == i ^ 2;
+
+                if extras[i].len() == 1 && extras[i] == extras[j] {
+                    let kind = if in_line { UniqueRectangleType::Type2 } else { UniqueRectangleType::Type5 };
+                    push(kind, seeing(&[i, j], extras[i][0]));
+                }
+
+                if in_line {
+                    let roofs = [cells[i], cells[j]];
+                    let shared = Board::all_groups()
+                        .into_iter()
+                        .filter(|group| group.contains(&roofs[0]) && group.contains(&roofs[1]))
+                        .collect::<Vec<_>>();
+
+                    for group in &shared {
+                        // Type 3: one of the roofs holds one of their
+                        // extra digits, which makes it one more cell
+                        // of a naked subset with the others.
+                        let extra = extras[i].iter().chain(&extras[j]).copied().collect::<BTreeSet<usize>>();
+                        let others = group
+                            .iter()
+                            .filter(|idx| !roofs.contains(idx) && matches!(board.cells[**idx], Cell::Unsolved(_)))
+                            .copied()
+                            .collect::<Vec<usize>>();
+
+                        for size in 1..=others.len().min(3) {
+                            for subset in combinations(&others, size) {
+                                let mut digits = extra.clone();
+                                for idx in &subset {
+                                    digits.extend(board.cells[*idx].digits());
+                                }
+                                if digits.len() != size + 1 {
+                                    continue;
+                                }
+
+                                push(UniqueRectangleType::Type3, group
+                                     .iter()
+                                     .filter(|idx| !roofs.contains(idx) && !subset.contains(idx))
+                                     .flat_map(|idx| digits
+                                               .iter()
+                                               .filter(|digit| board.cells[*idx].contains(**digit))
+                                               .map(|digit| (*idx, *digit)))
+                                     .collect());
+                            }
+                        }
+
+                        // Type 4: the roofs are the only places for x
+                        // in the group, so one of them is x and the
+                        // other can't be y.
+                        for (x, y) in [(a, b), (b, a)] {
+                            if places(board, group, x) == BTreeSet::from(roofs) {
+                                push(UniqueRectangleType::Type4, vec![(roofs[0], y), (roofs[1], y)]);
+                            }
+                        }
+                    }
+                } else {
+                    // Type 6: if x is confined to the rectangle in both
+                    // rows or both columns, it has to be in the two
+                    // cells without extras.
+                    let lines = [
+                        [Board::rows()[cells[0] / 9].clone(), Board::rows()[cells[2] / 9].clone()],
+                        [Board::columns()[cells[0] % 9].clone(), Board::columns()[cells[1] % 9].clone()],
+                    ];
+
+                    for x in [a, b] {
+                        let confined = lines.iter().any(|pair| pair.iter().all(|line| {
+                            places(board, line, x)
+                                .iter()
+                                .all(|idx| cells.contains(idx))
+                        }));
+
+                        if confined {
+                            push(UniqueRectangleType::Type6, vec![(cells[i], x), (cells[j], x)]);
+                        }
+                    }
+                }
+            },
+
+            [i, j, k] if extras[i].len() == 1 && extras[i] == extras[j] && extras[i] == extras[k] => {
+                push(UniqueRectangleType::Type5, seeing(&[i, j, k], extras[i][0]));
+            },
+
+            _ => (),
+        }
+    }
+
+    result
+}
+
+// Unique rectangle types 1 to 6.
+pub struct UniqueRectangle;
+
+impl Strategy for UniqueRectangle {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(UniqueRectangle {})
+    }
+
+    fn name(&self) -> String {
+        String::from("UniqueRectangle")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_unique_rectangles(board).into_iter().filter(|pattern| pattern.kind != UniqueRectangleType::Hidden))
+    }
+
+    fn requires_uniqueness(&self) -> bool {
+        true
+    }
+}
+
+// Hidden unique rectangles, which find the same deadly pattern through
+// strong links instead of extra digits.
+pub struct HiddenUniqueRectangle;
+
+impl Strategy for HiddenUniqueRectangle {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(HiddenUniqueRectangle {})
+    }
+
+    fn name(&self) -> String {
+        String::from("HiddenUniqueRectangle")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_unique_rectangles(board).into_iter().filter(|pattern| pattern.kind == UniqueRectangleType::Hidden))
+    }
+
+    fn requires_uniqueness(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,108 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::cell_names;
+use crate::combinations;
+use std::fmt;
+
+// Two cells that can each only be x or y, joined by a group with only
+// two places for x, one seeing each cell.  If neither cell were y
+// they would both be x, leaving nowhere in the group for x.  So one
+// of them is y, and y can be removed from every cell that sees both.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WWingPattern {
+    pub digit: usize,
+    pub link_digit: usize,
+    pub cells: [usize; 2],
+    pub link: [usize; 2],
+    pub eliminations: Vec<usize>,
+}
+
+impl fmt::Display for WWingPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} and {} are linked by {} at {} and {}, so {} is removed from {}",
+               cell_name(self.cells[0]), cell_name(self.cells[1]), self.link_digit,
+               cell_name(self.link[0]), cell_name(self.link[1]), self.digit,
+               cell_names(&self.eliminations))
+    }
+}
+
+impl Pattern for WWingPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+pub fn find_w_wings(board: &Board) -> Vec<WWingPattern> {
+    let mut result = Vec::new();
+
+    let bivalue = (0..81)
+        .filter(|idx| matches!(board.cells[*idx], Cell::Unsolved(_)) && board.cells[*idx].count() == 2)
+        .collect::<Vec<usize>>();
+
+    for pair in combinations(&bivalue, 2) {
+        let cells = [pair[0], pair[1]];
+
+        // Cells that see each other would be a naked pair.
+        if board.cells[cells[0]] != board.cells[cells[1]] || Board::sees(cells[0], cells[1]) {
+            continue;
+        }
+
+        let mut digits = board.cells[cells[0]].digits().into_iter().collect::<Vec<usize>>();
+        digits.sort_unstable();
+
+        for (link_digit, digit) in [(digits[0], digits[1]), (digits[1], digits[0])] {
+            let eliminations = (0..81)
+                .filter(|idx| Board::sees(*idx, cells[0])
+                        && Board::sees(*idx, cells[1])
+                        && board.cells[*idx].contains(digit))
+                .collect::<Vec<usize>>();
+
+            if eliminations.is_empty() {
+                continue;
+            }
+
+            for group in Board::all_groups() {
+                let places = board.places(&group, link_digit);
+                if places.len() != 2 || places.iter().any(|idx| cells.contains(idx)) {
+                    continue;
+                }
+
+                for link in [[places[0], places[1]], [places[1], places[0]]] {
+                    if Board::sees(link[0], cells[0]) && Board::sees(link[1], cells[1]) {
+                        result.push(WWingPattern {
+                            digit,
+                            link_digit,
+                            cells,
+                            link,
+                            eliminations: eliminations.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub struct WWing;
+
+impl Strategy for WWing {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(WWing {})
+    }
+
+    fn name(&self) -> String {
+        String::from("WWing")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_w_wings(board))
+    }
+}
@@ -0,0 +1,232 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::cell_names;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+
+// The kinds of loop an X-Cycle can form.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum XCycleKind {
+    // Strong and weak links alternate all the way round, so every
+    // weak link is really strong, and the digit can be removed from
+    // any cell that sees both ends of one.
+    Continuous,
+
+    // Two strong links meet at the start of the loop, so it has to
+    // hold the digit.
+    DiscontinuousStrong,
+
+    // Two weak links meet at the start of the loop, so it can't hold
+    // the digit.
+    DiscontinuousWeak,
+}
+
+// A loop of cells for one digit.  The path starts and ends on the
+// same cell, and strong says whether each link along it is strong.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct XCyclePattern {
+    pub digit: usize,
+    pub kind: XCycleKind,
+    pub path: Vec<usize>,
+    pub strong: Vec<bool>,
+    pub eliminations: Vec<usize>,
+    pub placement: Option<usize>,
+}
+
+impl fmt::Display for XCyclePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut path = cell_name(self.path[0]);
+        for (idx, strong) in self.path[1..].iter().zip(&self.strong) {
+            path += if *strong { " = " } else { " - " };
+            path += &cell_name(*idx);
+        }
+
+        match self.placement {
+            Some(idx) => write!(f, "{:?} loop on {} {} places it at {}",
+                                self.kind, self.digit, path, cell_name(idx)),
+            None => write!(f, "{:?} loop on {} {} removes it from {}",
+                           self.kind, self.digit, path,
+                           cell_names(&self.eliminations)),
+        }
+    }
+}
+
+impl Pattern for XCyclePattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+
+    fn placements(&self) -> Vec<Candidate> {
+        self.placement.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+// For each cell, the cells it has a strong link to for the digit: the
+// other place in a group where the digit has only two.
+fn strong_links(board: &Board, digit: usize) -> Vec<BTreeSet<usize>> {
+    let mut result = vec![BTreeSet::new(); 81];
+    for group in Board::all_groups() {
+        let places = board.places(&group, digit);
+        if places.len() == 2 {
+            result[places[0]].insert(places[1]);
+            result[places[1]].insert(places[0]);
+        }
+    }
+    result
+}
+
+// Search outwards from the start cell, assuming it does (on) or
+// doesn't hold the digit, following what each link implies: a cell
+// holding the digit rules out every cell it sees, and a cell without
+// it forces the other end of each of its strong links.  Returns the
+// shortest loop that gets back to the start cell with the goal value,
+// if there is one and it doesn't cross itself.
+fn find_loop(board: &Board, strong: &[BTreeSet<usize>], digit: usize,
+             start: usize, on: bool, goal: bool) -> Option<(Vec<usize>, Vec<bool>)> {
+    let mut parents: HashMap<(usize, bool), (usize, bool)> = HashMap::new();
+    let mut todo = VecDeque::from([(start, on, 0)]);
+
+    while let Some((idx, value, depth)) = todo.pop_front() {
+        let next = if value {
+            Board::all_neighbors(idx)
+                .into_iter()
+                .filter(|other| board.cells[*other].contains(digit))
+                .collect::<Vec<usize>>()
+        } else {
+            strong[idx].iter().copied().collect()
+        };
+
+        for other in next {
+            if other == start {
+                // Getting here sets the start to the opposite of this
+                // cell.  Two links back to the start is just one link
+                // there and back again.
+                if value == goal || depth < 2 {
+                    continue;
+                }
+
+                let mut path = vec![start, idx];
+                let mut strong = vec![!value];
+                let mut state = (idx, value);
+                while let Some(parent) = parents.get(&state) {
+                    path.push(parent.0);
+                    strong.push(!parent.1);
+                    state = *parent;
+                }
+                path.reverse();
+                strong.reverse();
+
+                let cells = path[1..].iter().collect::<BTreeSet<_>>();
+                if cells.len() != path.len() - 1 {
+                    return None;
+                }
+
+                return Some((path, strong));
+            }
+
+            if let Entry::Vacant(entry) = parents.entry((other, !value)) {
+                entry.insert((idx, value));
+                todo.push_back((other, !value, depth + 1));
+            }
+        }
+    }
+
+    None
+}
+
+pub fn find_x_cycles(board: &Board) -> Vec<XCyclePattern> {
+    let mut result = Vec::new();
+
+    for digit in 1..=9 {
+        let strong = strong_links(board, digit);
+        let mut loops = BTreeSet::new();
+
+        for start in 0..81 {
+            if !matches!(board.cells[start], Cell::Unsolved(_)) || !board.cells[start].contains(digit) {
+                continue;
+            }
+
+            // Without the digit the start is forced to have it.
+            if let Some((path, links)) = find_loop(board, &strong, digit, start, false, true) {
+                result.push(XCyclePattern {
+                    digit,
+                    kind: XCycleKind::DiscontinuousStrong,
+                    path,
+                    strong: links,
+                    eliminations: Vec::new(),
+                    placement: Some(start),
+                });
+            }
+
+            // With the digit the start is forced not to have it.
+            if let Some((path, links)) = find_loop(board, &strong, digit, start, true, false) {
+                result.push(XCyclePattern {
+                    digit,
+                    kind: XCycleKind::DiscontinuousWeak,
+                    path,
+                    strong: links,
+                    eliminations: vec![start],
+                    placement: None,
+                });
+            }
+
+            // Without the digit the start is forced to be without it
+            // again, which makes the loop continuous.  Every cell of
+            // the loop finds it, so only keep it once.
+            if let Some((path, links)) = find_loop(board, &strong, digit, start, false, false) {
+                if !loops.insert(path.iter().copied().collect::<BTreeSet<usize>>()) {
+                    continue;
+                }
+
+                let eliminations = (0..81)
+                    .filter(|idx| board.cells[*idx].contains(digit)
+                            && !path.contains(idx)
+                            && path
+                            .windows(2)
+                            .zip(&links)
+                            .any(|(ends, strong)| !strong
+                                 && Board::sees(*idx, ends[0])
+                                 && Board::sees(*idx, ends[1])))
+                    .collect::<Vec<usize>>();
+
+                if !eliminations.is_empty() {
+                    result.push(XCyclePattern {
+                        digit,
+                        kind: XCycleKind::Continuous,
+                        path,
+                        strong: links,
+                        eliminations,
+                        placement: None,
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// X-Cycles: loops of alternating strong and weak links for a single
+// digit.
+pub struct XCycle;
+
+impl Strategy for XCycle {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(XCycle {})
+    }
+
+    fn name(&self) -> String {
+        String::from("XCycle")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_x_cycles(board))
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::cell_names;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+// A chain of cells that can each only be one of two digits, where
+// each cell sees the next and shares a digit with it.  The chain is
+// kept as (cell index, digit) pairs, two per cell: the digit the cell
+// loses to the cell before it, then the digit it is left with, which
+// it passes on to the next.  The first cell either is the end digit,
+// or it forces the last cell to be, so the end digit can be removed
+// from every cell that sees both ends.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct XYChainPattern {
+    pub digit: usize,
+    pub chain: Vec<(usize, usize)>,
+    pub eliminations: Vec<usize>,
+}
+
+impl fmt::Display for XYChainPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chain = self.chain
+            .chunks(2)
+            .map(|pair| format!("{} ({}={})", cell_name(pair[0].0), pair[0].1, pair[1].1))
+            .collect::<Vec<_>>()
+            .join(" - ");
+        write!(f, "{} removes {} from {}",
+               chain, self.digit,
+               cell_names(&self.eliminations))
+    }
+}
+
+impl Pattern for XYChainPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+fn is_bivalue(cell: &Cell) -> bool {
+    matches!(cell, Cell::Unsolved(_)) && cell.count() == 2
+}
+
+// The digit in a bivalue cell other than the one passed in.
+fn other_digit(cell: &Cell, digit: usize) -> usize {
+    cell.digits().into_iter().find(|other| *other != digit).unwrap()
+}
+
+pub fn find_xy_chains(board: &Board) -> Vec<XYChainPattern> {
+    let mut result = Vec::new();
+
+    for start in (0..81).filter(|idx| is_bivalue(&board.cells[*idx])) {
+        let mut digits = board.cells[start].digits().into_iter().collect::<Vec<usize>>();
+        digits.sort_unstable();
+
+        for digit in digits {
+            // Follow the chain out from the start, assuming it isn't
+            // the digit, keeping the shortest way to reach each cell
+            // with each digit left over.
+            let first = (start, other_digit(&board.cells[start], digit));
+            let mut parents = HashMap::new();
+            let mut todo = VecDeque::from([first]);
+
+            while let Some((idx, passed)) = todo.pop_front() {
+                for next in Board::all_neighbors(idx)
+                    .into_iter()
+                    .filter(|next| *next != start
+                            && is_bivalue(&board.cells[*next])
+                            && board.cells[*next].contains(passed))
+                {
+                    let state = (next, other_digit(&board.cells[next], passed));
+                    if let Entry::Vacant(entry) = parents.entry(state) {
+                        entry.insert((idx, passed));
+                        todo.push_back(state);
+                    }
+                }
+            }
+
+            // Every cell left holding the digit ends a chain.  Each
+            // chain is found from both ends, so only keep it once.
+            let mut ends = parents
+                .keys()
+                .filter(|(end, left)| *left == digit && *end > start)
+                .copied()
+                .collect::<Vec<_>>();
+            ends.sort_unstable();
+
+            for end in ends {
+                let mut cells = vec![end];
+                let mut state = end;
+                while let Some(parent) = parents.get(&state) {
+                    cells.push(*parent);
+                    state = *parent;
+                }
+                cells.reverse();
+
+                // The shortest path to a state can still visit the
+                // same cell twice with different digits.
+                let mut visited = cells.iter().map(|(idx, _)| *idx).collect::<Vec<usize>>();
+                visited.sort_unstable();
+                visited.dedup();
+                if visited.len() != cells.len() {
+                    continue;
+                }
+
+                let eliminations = (0..81)
+                    .filter(|idx| Board::sees(*idx, start)
+                            && Board::sees(*idx, end.0)
+                            && board.cells[*idx].contains(digit))
+                    .collect::<Vec<usize>>();
+
+                if eliminations.is_empty() {
+                    continue;
+                }
+
+                let mut chain = Vec::new();
+                let mut lost = digit;
+                for (idx, left) in cells {
+                    chain.push((idx, lost));
+                    chain.push((idx, left));
+                    lost = left;
+                }
+
+                result.push(XYChainPattern {
+                    digit,
+                    chain,
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+// XY-Chains: the longer relative of the XY-Wing.
+pub struct XYChain;
+
+impl Strategy for XYChain {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(XYChain {})
+    }
+
+    fn name(&self) -> String {
+        String::from("XYChain")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_xy_chains(board))
+    }
+}
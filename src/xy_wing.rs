@@ -0,0 +1,136 @@
+use crate::Board;
+use crate::Candidate;
+use crate::Cell;
+use crate::Contradiction;
+use crate::Pattern;
+use crate::Strategy;
+use crate::apply_patterns;
+use crate::cell_name;
+use crate::cell_names;
+use crate::combinations;
+use std::collections::HashSet;
+use std::fmt;
+
+// A pivot cell and two pincer cells it sees, where whichever digit
+// the pivot turns out to be forces one of the pincers to be the
+// digit z.  So z can be removed from every cell that sees both
+// pincers (and, for an XYZ-Wing, the pivot too, since it can also be
+// z).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WingPattern {
+    pub digit: usize,
+    pub pivot: usize,
+    pub pincers: [usize; 2],
+    pub eliminations: Vec<usize>,
+}
+
+impl fmt::Display for WingPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pivot {} with pincers {} and {} removes {} from {}",
+               cell_name(self.pivot), cell_name(self.pincers[0]), cell_name(self.pincers[1]), self.digit,
+               cell_names(&self.eliminations))
+    }
+}
+
+impl Pattern for WingPattern {
+    fn eliminations(&self) -> Vec<Candidate> {
+        self.eliminations.iter().map(|idx| (*idx, self.digit)).collect()
+    }
+}
+
+fn is_unsolved(cell: &Cell, count: usize) -> bool {
+    matches!(cell, Cell::Unsolved(_)) && cell.count() == count
+}
+
+// Find wings whose pivot has pivot_count digits: 2 for an XY-Wing, 3
+// for an XYZ-Wing.  Each pincer has two digits, one shared with the
+// pivot and the digit z, and between them they cover the pivot's
+// digits.
+pub fn find_wings(board: &Board, pivot_count: usize) -> Vec<WingPattern> {
+    let mut result = Vec::new();
+
+    for pivot in (0..81).filter(|idx| is_unsolved(&board.cells[*idx], pivot_count)) {
+        let pivot_digits = board.cells[pivot].digits();
+
+        let pincers = Board::all_neighbors(pivot)
+            .into_iter()
+            .filter(|idx| is_unsolved(&board.cells[*idx], 2))
+            .collect::<Vec<usize>>();
+
+        for pair in combinations(&pincers, 2) {
+            let a = board.cells[pair[0]].digits();
+            let b = board.cells[pair[1]].digits();
+
+            // The pincers have to share exactly one digit, z.
+            let shared = a.intersection(&b).copied().collect::<Vec<usize>>();
+            if shared.len() != 1 || a == b {
+                continue;
+            }
+            let z = shared[0];
+
+            // Between them the pincers hold exactly the pivot's
+            // digits plus z for an XY-Wing, or exactly the pivot's
+            // digits for an XYZ-Wing.
+            let mut union = a.union(&b).copied().collect::<HashSet<usize>>();
+            if pivot_count == 2 {
+                if pivot_digits.contains(&z) {
+                    continue;
+                }
+                union.remove(&z);
+            }
+            if union != pivot_digits {
+                continue;
+            }
+
+            let eliminations = (0..81)
+                .filter(|idx| Board::sees(*idx, pair[0])
+                        && Board::sees(*idx, pair[1])
+                        && (pivot_count == 2 || Board::sees(*idx, pivot))
+                        && board.cells[*idx].contains(z))
+                .collect::<Vec<usize>>();
+
+            if !eliminations.is_empty() {
+                result.push(WingPattern {
+                    digit: z,
+                    pivot,
+                    pincers: [pair[0], pair[1]],
+                    eliminations,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+pub struct XYWing;
+
+impl Strategy for XYWing {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(XYWing {})
+    }
+
+    fn name(&self) -> String {
+        String::from("XYWing")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_wings(board, 2))
+    }
+}
+
+pub struct XYZWing;
+
+impl Strategy for XYZWing {
+    fn new() -> Box<dyn Strategy> {
+        Box::new(XYZWing {})
+    }
+
+    fn name(&self) -> String {
+        String::from("XYZWing")
+    }
+
+    fn deduce(&self, board: &Board) -> Result<(Board, Vec<String>), Contradiction> {
+        apply_patterns(&self.name(), board, find_wings(board, 3))
+    }
+}